@@ -0,0 +1,137 @@
+use std::fmt;
+use std::io;
+use std::num::{ParseFloatError, ParseIntError};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+pub type GenResult<T> = Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    /// Reading an input or data file failed.
+    Io {
+        path: Option<PathBuf>,
+        source: io::Error,
+    },
+    /// A line of puzzle input did not have the expected shape.
+    Parse(ParseError),
+    /// Anything else, described in prose.
+    Msg(String),
+}
+
+/// Where in the input parsing failed and what was expected there.
+#[derive(Debug)]
+pub struct ParseError {
+    pub path: Option<PathBuf>,
+    /// 1-based line number.
+    pub line: usize,
+    /// Byte range within `source_line` that the diagnostic points at.
+    pub span: Range<usize>,
+    pub expected: String,
+    pub source_line: String,
+}
+
+impl Error {
+    /// Records the file the failing input came from, for diagnostics.
+    pub fn with_path(mut self, new_path: &Path) -> Self {
+        match &mut self {
+            Error::Io { path, .. } => {
+                path.get_or_insert_with(|| new_path.to_path_buf());
+            }
+            Error::Parse(e) => {
+                e.path.get_or_insert_with(|| new_path.to_path_buf());
+            }
+            Error::Msg(_) => {}
+        }
+        self
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io {
+                path: Some(path),
+                source,
+            } => write!(f, "Could not read {}: {}", path.display(), source),
+            Error::Io { path: None, source } => write!(f, "{}", source),
+            Error::Parse(e) => write!(f, "{}", e),
+            Error::Msg(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Renders as a caret-underlined snippet of the offending line:
+///
+/// ```text
+/// expected '|'
+///  --> day4/input.txt:2:23
+///   |
+/// 2 | Card 2: 13 32 20 16 61
+///   |                       ^
+/// ```
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let start = self.span.start.min(self.source_line.len());
+        let end = self.span.end.clamp(start, self.source_line.len());
+
+        let pad = self.source_line.get(..start).unwrap_or("").chars().count();
+        let width = self
+            .source_line
+            .get(start..end)
+            .map(|s| s.chars().count())
+            .unwrap_or(0)
+            .max(1);
+
+        let location = self
+            .path
+            .as_ref()
+            .map(|p| p.display().to_string())
+            .unwrap_or_else(|| "<input>".to_string());
+        let gutter = " ".repeat(self.line.to_string().len());
+
+        writeln!(f, "expected {}", self.expected)?;
+        writeln!(f, "{}--> {}:{}:{}", gutter, location, self.line, pad + 1)?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, self.source_line)?;
+        write!(f, "{} | {}{}", gutter, " ".repeat(pad), "^".repeat(width))
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Parse(e)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(source: io::Error) -> Self {
+        Error::Io { path: None, source }
+    }
+}
+
+impl From<String> for Error {
+    fn from(msg: String) -> Self {
+        Error::Msg(msg)
+    }
+}
+
+impl From<&str> for Error {
+    fn from(msg: &str) -> Self {
+        Error::Msg(msg.to_string())
+    }
+}
+
+impl From<ParseIntError> for Error {
+    fn from(e: ParseIntError) -> Self {
+        Error::Msg(e.to_string())
+    }
+}
+
+impl From<ParseFloatError> for Error {
+    fn from(e: ParseFloatError) -> Self {
+        Error::Msg(e.to_string())
+    }
+}
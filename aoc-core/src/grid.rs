@@ -0,0 +1,225 @@
+//! A rectangular grid of cells, the shape most puzzle inputs come in.
+
+use crate::{lines, GenResult};
+use std::ops::{Index, IndexMut, Range};
+
+/// A cell's position: row from the top, column from the left, both from 0.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pos {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Pos {
+    pub fn new(row: usize, col: usize) -> Self {
+        Pos { row, col }
+    }
+}
+
+const ORTHOGONAL: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+const ALL_AROUND: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    /// Row by row.
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// [`Grid::parse`] for lines of any length: the grid is as wide as the
+    /// longest, and shorter ones are padded with `fill`.
+    pub fn parse_ragged(
+        input: &str,
+        expected: &str,
+        fill: T,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> GenResult<Self> {
+        let mut rows = vec![];
+        for line in lines(input) {
+            let row = line
+                .text
+                .char_indices()
+                .map(|(i, c)| cell(c).ok_or_else(|| line.error_at(i..i + c.len_utf8(), expected)))
+                .collect::<GenResult<Vec<T>>>()?;
+            rows.push(row);
+        }
+
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for mut row in rows {
+            row.resize(width, fill.clone());
+            cells.append(&mut row);
+        }
+
+        Ok(Grid {
+            width,
+            height,
+            cells,
+        })
+    }
+}
+
+impl Grid<char> {
+    /// One cell per character of each line.
+    pub fn parse_chars(input: &str) -> GenResult<Self> {
+        Grid::parse(input, "any character", Some)
+    }
+}
+
+impl<T> Grid<T> {
+    /// One cell per character of each line, converted by `cell`; a
+    /// character it returns `None` for is an error expecting `expected`.
+    /// Every line must be as long as the first.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> GenResult<Self> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+
+        for line in lines(input) {
+            let mut row_width = 0;
+            for (i, c) in line.text.char_indices() {
+                let value = cell(c).ok_or_else(|| line.error_at(i..i + c.len_utf8(), expected))?;
+                cells.push(value);
+                row_width += 1;
+            }
+
+            let width = *width.get_or_insert(row_width);
+            if row_width != width {
+                return Err(line.error(line.text, format!("a row of {} characters", width)));
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.row < self.height && pos.col < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.row * self.width + pos.col])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.row * self.width + pos.col])
+        } else {
+            None
+        }
+    }
+
+    /// The position `rows` down and `cols` right of `pos`, if it is in the
+    /// grid.
+    pub fn offset(&self, pos: Pos, rows: isize, cols: isize) -> Option<Pos> {
+        let row = pos.row.checked_add_signed(rows)?;
+        let col = pos.col.checked_add_signed(cols)?;
+        let moved = Pos { row, col };
+        self.contains(moved).then_some(moved)
+    }
+
+    /// The cells above, left, right and below `pos` that are in the grid.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ORTHOGONAL
+            .iter()
+            .filter_map(move |&(dr, dc)| self.offset(pos, dr, dc))
+    }
+
+    /// The cells around `pos`, diagonals included, that are in the grid.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ALL_AROUND
+            .iter()
+            .filter_map(move |&(dr, dc)| self.offset(pos, dr, dc))
+    }
+
+    /// Row `row`, or `None` past the last row.
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        (row < self.height).then(|| &self.cells[row * self.width..(row + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` rejects 0, and a zero-width grid has no cells anyway.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Column `col` from top to bottom; empty past the last column.
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        let rows = if col < self.width { self.height } else { 0 };
+        (0..rows).map(move |row| &self.cells[row * self.width + col])
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.cells.iter().enumerate().map(|(i, cell)| {
+            let pos = Pos::new(i / self.width, i % self.width);
+            (pos, cell)
+        })
+    }
+
+    /// The cells in `rows` and `cols`, row by row, cut down to the grid.
+    pub fn region(
+        &self,
+        rows: Range<usize>,
+        cols: Range<usize>,
+    ) -> impl Iterator<Item = (Pos, &T)> {
+        let rows = rows.start.min(self.height)..rows.end.min(self.height);
+        let cols = cols.start.min(self.width)..cols.end.min(self.width);
+        rows.flat_map(move |row| cols.clone().map(move |col| Pos::new(row, col)))
+            .map(|pos| (pos, &self[pos]))
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    /// Panics if `pos` is outside the grid; see [`Grid::get`].
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside a {}x{} grid", pos, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside a {}x{} grid", pos, width, height))
+    }
+}
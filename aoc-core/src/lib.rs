@@ -0,0 +1,35 @@
+//! Shared helpers for the Advent of Code 2023 solutions: the common result
+//! and error types, input loading, the line-oriented parsing helpers
+//! every day ends up needing and a grid for the puzzles laid out in 2D.
+
+use std::fs::read_to_string;
+use std::io::{self, Read};
+use std::path::Path;
+
+mod error;
+mod grid;
+mod line;
+mod solution;
+
+pub use error::{Error, GenResult, ParseError};
+pub use grid::{Grid, Pos};
+pub use line::{lines, Line};
+pub use solution::{DynSolution, Solution};
+
+/// Reads the whole input at `path`, where `-` means standard input.
+pub fn read_input(path: impl AsRef<Path>) -> GenResult<String> {
+    let path = path.as_ref();
+    if path == Path::new("-") {
+        let mut contents = String::new();
+        io::stdin().read_to_string(&mut contents)?;
+        return Ok(contents);
+    }
+
+    read_to_string(path).map_err(|e| Error::from(e).with_path(path))
+}
+
+/// Reads the input at `path` (see [`read_input`]) and returns its lines
+/// without line terminators.
+pub fn read_lines(path: impl AsRef<Path>) -> GenResult<Vec<String>> {
+    Ok(read_input(path)?.lines().map(String::from).collect())
+}
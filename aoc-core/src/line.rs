@@ -0,0 +1,75 @@
+use crate::{Error, GenResult, ParseError};
+use std::ops::Range;
+use std::str::FromStr;
+
+/// One line of puzzle input along with its 1-based line number, so parsers
+/// can point their errors at the exact columns that went wrong.
+#[derive(Clone, Copy, Debug)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+/// Splits `input` into numbered [`Line`]s.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(i, text)| Line {
+        number: i + 1,
+        text,
+    })
+}
+
+impl<'a> Line<'a> {
+    /// The byte range `part` occupies in this line. `part` must be a
+    /// subslice of `self.text`; anything else spans the whole line.
+    pub fn span_of(&self, part: &str) -> Range<usize> {
+        let base = self.text.as_ptr() as usize;
+        let start = part.as_ptr() as usize;
+
+        if start >= base && start + part.len() <= base + self.text.len() {
+            let offset = start - base;
+            offset..offset + part.len()
+        } else {
+            0..self.text.len()
+        }
+    }
+
+    /// An error pointing at `part`, a subslice of this line.
+    pub fn error(&self, part: &str, expected: impl Into<String>) -> Error {
+        self.error_at(self.span_of(part), expected)
+    }
+
+    pub fn error_at(&self, span: Range<usize>, expected: impl Into<String>) -> Error {
+        Error::Parse(ParseError {
+            path: None,
+            line: self.number,
+            span,
+            expected: expected.into(),
+            source_line: self.text.to_string(),
+        })
+    }
+
+    /// Splits `part` on the first `delim`. If it is missing the error points
+    /// just past the end of `part`, where the delimiter was expected.
+    pub fn split_pair(&self, part: &'a str, delim: char) -> GenResult<(&'a str, &'a str)> {
+        part.split_once(delim).ok_or_else(|| {
+            let end = self.span_of(part).end;
+            self.error_at(end..end + 1, format!("'{}'", delim))
+        })
+    }
+
+    /// Parses `token`, a subslice of this line, describing it as `what` on
+    /// failure (e.g. "a number").
+    pub fn parse<T: FromStr>(&self, token: &str, what: &str) -> GenResult<T> {
+        token
+            .trim()
+            .parse::<T>()
+            .map_err(|_| self.error(token, what))
+    }
+
+    /// Parses every whitespace-separated token in `part` as a number.
+    pub fn parse_nums<T: FromStr>(&self, part: &'a str) -> GenResult<Vec<T>> {
+        part.split_whitespace()
+            .map(|num| self.parse(num, "a number"))
+            .collect()
+    }
+}
@@ -0,0 +1,44 @@
+use crate::GenResult;
+use std::any::Any;
+use std::fmt::Display;
+
+/// A day's puzzle: the input is parsed once and both parts are answered
+/// from the parsed form.
+pub trait Solution {
+    type Parsed;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> GenResult<Self::Parsed>;
+    fn part1(parsed: &Self::Parsed) -> GenResult<Self::Answer1>;
+    fn part2(parsed: &Self::Parsed) -> GenResult<Self::Answer2>;
+}
+
+/// Object-safe view of a [`Solution`] so the runner can keep every day in
+/// one table. Answers are rendered with their `Display` impl.
+pub trait DynSolution: Sync {
+    fn parse(&self, input: &str) -> GenResult<Box<dyn Any>>;
+    fn solve(&self, parsed: &dyn Any, part: u32) -> GenResult<String>;
+}
+
+impl<S> DynSolution for S
+where
+    S: Solution + Sync,
+    S::Parsed: 'static,
+{
+    fn parse(&self, input: &str) -> GenResult<Box<dyn Any>> {
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn solve(&self, parsed: &dyn Any, part: u32) -> GenResult<String> {
+        let parsed = parsed
+            .downcast_ref::<S::Parsed>()
+            .ok_or("Parsed input does not belong to this solution")?;
+
+        match part {
+            1 => Ok(S::part1(parsed)?.to_string()),
+            2 => Ok(S::part2(parsed)?.to_string()),
+            _ => Err(format!("Invalid part {}, expected 1 or 2", part).into()),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use aoc_core::{Error, Grid, Pos};
+
+#[test]
+fn grids_parse_one_cell_per_character() {
+    let grid = Grid::parse_chars("ab٣\ndef\n").unwrap();
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid[Pos::new(0, 2)], '٣');
+    assert_eq!(grid.get(Pos::new(1, 3)), None);
+    assert_eq!(grid.get(Pos::new(2, 0)), None);
+
+    let Error::Parse(e) = Grid::parse_chars("abc\nde\n").unwrap_err() else {
+        panic!("expected a parse error");
+    };
+    assert_eq!((e.line, e.expected.as_str()), (2, "a row of 3 characters"));
+    let ragged = Grid::parse_ragged("ab\nc\ndef\n", "any character", '.', Some).unwrap();
+    assert_eq!(
+        ragged.rows().collect::<Vec<_>>(),
+        [['a', 'b', '.'], ['c', '.', '.'], ['d', 'e', 'f']]
+    );
+
+    let Error::Parse(e) = Grid::parse("01\n1x\n", "0 or 1", |c| c.to_digit(2)).unwrap_err() else {
+        panic!("expected a parse error");
+    };
+    assert_eq!((e.line, e.span), (2, 1..2));
+
+    let empty = Grid::parse_chars("").unwrap();
+    assert_eq!(
+        (empty.width(), empty.height(), empty.rows().count()),
+        (0, 0, 0)
+    );
+}
+
+#[test]
+fn neighbors_stay_inside_the_grid() {
+    let grid = Grid::new(3, 2, 0u8);
+    let corner: Vec<_> = grid.neighbors8(Pos::new(0, 0)).collect();
+    assert_eq!(corner, [Pos::new(0, 1), Pos::new(1, 0), Pos::new(1, 1)]);
+    assert_eq!(grid.neighbors8(Pos::new(1, 1)).count(), 5);
+
+    let middle: Vec<_> = grid.neighbors4(Pos::new(0, 1)).collect();
+    assert_eq!(middle, [Pos::new(0, 0), Pos::new(0, 2), Pos::new(1, 1)]);
+    assert_eq!(grid.offset(Pos::new(1, 2), 0, 1), None);
+    assert_eq!(grid.offset(Pos::new(1, 2), -1, -2), Some(Pos::new(0, 0)));
+}
+
+#[test]
+fn rows_columns_and_regions_iterate_in_reading_order() {
+    let mut grid = Grid::parse("123\n456\n789\n", "a digit", |c| c.to_digit(10)).unwrap();
+    grid[Pos::new(1, 1)] = 0;
+
+    let rows: Vec<_> = grid.rows().collect();
+    assert_eq!(rows, [[1, 2, 3], [4, 0, 6], [7, 8, 9]]);
+    assert_eq!(grid.row(2), Some(&[7, 8, 9][..]));
+    assert_eq!(grid.row(3), None);
+    assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), [3, 6, 9]);
+    assert_eq!(grid.column(3).count(), 0);
+
+    // Regions are clipped to the grid.
+    let region: Vec<_> = grid.region(1..5, 2..9).map(|(pos, &v)| (pos, v)).collect();
+    assert_eq!(region, [(Pos::new(1, 2), 6), (Pos::new(2, 2), 9)]);
+    let sum: u32 = grid.iter().map(|(_, &v)| v).sum();
+    assert_eq!(sum, 40);
+    assert_eq!(grid.iter().nth(4).map(|(pos, _)| pos), Some(Pos::new(1, 1)));
+}
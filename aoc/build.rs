@@ -0,0 +1,70 @@
+//! Fingerprints each day's sources (plus aoc-core, which every day builds
+//! on) so cached answers are invalidated whenever a solver changes.
+
+#[path = "src/sha256.rs"]
+#[allow(dead_code)]
+mod sha256;
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let root = manifest_dir.parent().unwrap();
+
+    let mut core = vec![];
+    collect_sources(&root.join("aoc-core"), &mut core);
+
+    let mut days = vec![];
+    for entry in fs::read_dir(root).unwrap() {
+        let path = entry.unwrap().path();
+        let name = path.file_name().unwrap().to_string_lossy().to_string();
+        if let Some(Ok(number)) = name.strip_prefix("day").map(str::parse::<u32>) {
+            days.push((number, path));
+        }
+    }
+    days.sort();
+
+    let mut generated = String::from("pub const SOLVER_VERSIONS: &[(u32, &str)] = &[\n");
+    for (number, dir) in &days {
+        let mut files = core.clone();
+        collect_sources(dir, &mut files);
+        files.sort();
+
+        let mut contents = vec![];
+        for file in &files {
+            println!("cargo:rerun-if-changed={}", file.display());
+            contents.extend_from_slice(
+                file.strip_prefix(root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .as_bytes(),
+            );
+            contents.extend(fs::read(file).unwrap());
+        }
+
+        let version = sha256::sha256_hex(&contents);
+        generated.push_str(&format!("    ({}, \"{}\"),\n", number, &version[..16]));
+        println!("cargo:rerun-if-changed={}", dir.display());
+    }
+    generated.push_str("];\n");
+    // New days are registered in this manifest, which brings in their sources.
+    println!("cargo:rerun-if-changed=Cargo.toml");
+
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("solver_versions.rs");
+    fs::write(out, generated).unwrap();
+}
+
+fn collect_sources(dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            if path.file_name().is_some_and(|n| n != "target") {
+                collect_sources(&path, files);
+            }
+        } else if path.extension().is_some_and(|e| e == "rs") || path.ends_with("Cargo.toml") {
+            files.push(path);
+        }
+    }
+}
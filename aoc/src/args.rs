@@ -0,0 +1,75 @@
+use aoc_core::GenResult;
+use std::str::FromStr;
+
+/// A minimal command line parser: positionals are consumed in order and
+/// `--name value` / `--flag` options may appear anywhere.
+pub struct Args {
+    args: Vec<String>,
+}
+
+impl Args {
+    pub fn new(args: Vec<String>) -> Self {
+        Args { args }
+    }
+
+    pub fn from_env() -> Self {
+        Args::new(std::env::args().skip(1).collect())
+    }
+
+    /// Removes and returns the next positional argument.
+    pub fn positional(&mut self) -> Option<String> {
+        let index = self.args.iter().position(|a| !a.starts_with("--"))?;
+        Some(self.args.remove(index))
+    }
+
+    /// Removes `--name` and reports whether it was present.
+    pub fn flag(&mut self, name: &str) -> bool {
+        let key = format!("--{}", name);
+        match self.args.iter().position(|a| *a == key) {
+            Some(index) => {
+                self.args.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes `--name value` and returns the value, if present.
+    pub fn value(&mut self, name: &str) -> GenResult<Option<String>> {
+        let key = format!("--{}", name);
+        let Some(index) = self.args.iter().position(|a| *a == key) else {
+            return Ok(None);
+        };
+
+        if index + 1 >= self.args.len() {
+            return Err(format!("Expected a value after {}", key).into());
+        }
+
+        let value = self.args.remove(index + 1);
+        self.args.remove(index);
+        Ok(Some(value))
+    }
+
+    /// Like [`Args::value`], parsing the value as a `T`.
+    pub fn parsed<T>(&mut self, name: &str) -> GenResult<Option<T>>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display,
+    {
+        self.value(name)?
+            .map(|v| {
+                v.parse::<T>()
+                    .map_err(|e| format!("Invalid value for --{}: {} ({})", name, v, e).into())
+            })
+            .transpose()
+    }
+
+    /// Fails if any arguments were left unconsumed.
+    pub fn finish(self) -> GenResult<()> {
+        if self.args.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("Unexpected arguments: {}", self.args.join(" ")).into())
+        }
+    }
+}
@@ -0,0 +1,204 @@
+//! Repeated timing of each day's parse and parts, and the tab-separated
+//! results file used to compare one run against another.
+
+use crate::alloc::allocations;
+use crate::days::Day;
+use aoc_core::{read_input, GenResult};
+use std::fmt::Write as _;
+use std::fs;
+use std::hint::black_box;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+const HEADER: &str = "day\tstage\titerations\tmin_ns\tmedian_ns\tp95_ns\tallocs";
+
+/// Slowdowns smaller than this are timer noise, whatever the percentage.
+const NOISE_FLOOR: Duration = Duration::from_micros(1);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchResult {
+    pub day: u32,
+    /// `parse`, `part1` or `part2`.
+    pub stage: String,
+    pub iterations: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    /// Allocations made by a single iteration (the median across runs).
+    pub allocs: u64,
+}
+
+/// Parses the day's input and runs each part `iterations` times.
+pub fn bench_day(day: &Day, input_path: &Path, iterations: usize) -> GenResult<Vec<BenchResult>> {
+    let input = read_input(input_path)?;
+    let iterations = iterations.max(1);
+
+    let parse = measure(iterations, || {
+        black_box(day.solution.parse(&input)).map(|_| ())
+    })
+    .map_err(|e| e.with_path(input_path))?;
+
+    let parsed = day
+        .solution
+        .parse(&input)
+        .map_err(|e| e.with_path(input_path))?;
+    let part1 = measure(iterations, || {
+        black_box(day.solution.solve(parsed.as_ref(), 1)).map(|_| ())
+    })?;
+    let part2 = measure(iterations, || {
+        black_box(day.solution.solve(parsed.as_ref(), 2)).map(|_| ())
+    })?;
+
+    Ok([("parse", parse), ("part1", part1), ("part2", part2)]
+        .into_iter()
+        .map(|(stage, (times, allocs))| summarize(day.number, stage, times, allocs))
+        .collect())
+}
+
+fn measure(
+    iterations: usize,
+    mut f: impl FnMut() -> GenResult<()>,
+) -> GenResult<(Vec<Duration>, Vec<u64>)> {
+    let mut times = Vec::with_capacity(iterations);
+    let mut allocs = Vec::with_capacity(iterations);
+
+    for _ in 0..iterations {
+        let allocs_before = allocations();
+        let start = Instant::now();
+        f()?;
+        times.push(start.elapsed());
+        allocs.push(allocations() - allocs_before);
+    }
+
+    Ok((times, allocs))
+}
+
+fn summarize(day: u32, stage: &str, mut times: Vec<Duration>, mut allocs: Vec<u64>) -> BenchResult {
+    times.sort();
+    allocs.sort();
+
+    BenchResult {
+        day,
+        stage: stage.to_string(),
+        iterations: times.len(),
+        min: times[0],
+        median: percentile(&times, 50),
+        p95: percentile(&times, 95),
+        allocs: percentile(&allocs, 50),
+    }
+}
+
+/// Nearest-rank percentile of already sorted, non-empty `values`.
+fn percentile<T: Copy>(values: &[T], pct: usize) -> T {
+    let rank = (values.len() * pct).div_ceil(100);
+    values[rank.clamp(1, values.len()) - 1]
+}
+
+pub fn format_table(results: &[BenchResult]) -> String {
+    let mut out = format!(
+        "{:>3}  {:<6} {:>12} {:>12} {:>12} {:>10}\n",
+        "day", "stage", "min", "median", "p95", "allocs"
+    );
+
+    for r in results {
+        let _ = writeln!(
+            out,
+            "{:>3}  {:<6} {:>12} {:>12} {:>12} {:>10}",
+            r.day,
+            r.stage,
+            format!("{:.1?}", r.min),
+            format!("{:.1?}", r.median),
+            format!("{:.1?}", r.p95),
+            r.allocs
+        );
+    }
+
+    out
+}
+
+pub fn write_results(path: &Path, results: &[BenchResult]) -> GenResult<()> {
+    let mut out = format!("{}\n", HEADER);
+    for r in results {
+        let _ = writeln!(
+            out,
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            r.day,
+            r.stage,
+            r.iterations,
+            r.min.as_nanos(),
+            r.median.as_nanos(),
+            r.p95.as_nanos(),
+            r.allocs
+        );
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, out).map_err(|e| aoc_core::Error::from(e).with_path(path))
+}
+
+pub fn read_results(path: &Path) -> GenResult<Vec<BenchResult>> {
+    let contents = read_input(path)?;
+    let mut lines = contents.lines();
+
+    if lines.next() != Some(HEADER) {
+        return Err(format!("{} is not a bench results file", path.display()).into());
+    }
+
+    lines
+        .filter(|line| !line.is_empty())
+        .map(|line| match line.split('\t').collect::<Vec<_>>()[..] {
+            [day, stage, iterations, min, median, p95, allocs] => Ok(BenchResult {
+                day: day.parse()?,
+                stage: stage.to_string(),
+                iterations: iterations.parse()?,
+                min: Duration::from_nanos(min.parse()?),
+                median: Duration::from_nanos(median.parse()?),
+                p95: Duration::from_nanos(p95.parse()?),
+                allocs: allocs.parse()?,
+            }),
+            _ => Err(format!("Malformed bench result in {}: {}", path.display(), line).into()),
+        })
+        .collect()
+}
+
+/// Describes every stage whose median time grew by more than `threshold`
+/// (a fraction, 0.1 = 10%) or that allocates more than in `baseline`.
+pub fn regressions(
+    baseline: &[BenchResult],
+    current: &[BenchResult],
+    threshold: f64,
+) -> Vec<String> {
+    let mut found = vec![];
+
+    for cur in current {
+        let Some(old) = baseline
+            .iter()
+            .find(|b| b.day == cur.day && b.stage == cur.stage)
+        else {
+            continue;
+        };
+
+        let limit = old.median.as_secs_f64() * (1.0 + threshold);
+        if cur.median.as_secs_f64() > limit && cur.median > old.median + NOISE_FLOOR {
+            found.push(format!(
+                "day {} {}: median {:.1?} -> {:.1?} ({:+.0}%)",
+                cur.day,
+                cur.stage,
+                old.median,
+                cur.median,
+                (cur.median.as_secs_f64() / old.median.as_secs_f64() - 1.0) * 100.0
+            ));
+        }
+
+        if cur.allocs > old.allocs {
+            found.push(format!(
+                "day {} {}: allocations {} -> {}",
+                cur.day, cur.stage, old.allocs, cur.allocs
+            ));
+        }
+    }
+
+    found
+}
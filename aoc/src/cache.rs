@@ -0,0 +1,118 @@
+//! On-disk answer cache keyed by day, part, solver version and the SHA-256
+//! of the input, so re-running unchanged days is free.
+
+use aoc_core::{Error, GenResult};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const HEADER: &str = "day\tpart\tsolver_version\tinput_sha256\tanswer";
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    pub day: u32,
+    pub part: u32,
+    pub solver_version: String,
+    pub input_sha256: String,
+}
+
+pub struct Cache {
+    path: PathBuf,
+    entries: HashMap<CacheKey, String>,
+    dirty: bool,
+}
+
+impl Cache {
+    /// Loads the cache at `path`; a missing file is an empty cache.
+    pub fn load(path: impl Into<PathBuf>) -> GenResult<Self> {
+        let path = path.into();
+        let mut entries = HashMap::new();
+
+        if path.exists() {
+            let contents =
+                fs::read_to_string(&path).map_err(|e| Error::from(e).with_path(&path))?;
+            let mut lines = contents.lines();
+
+            // A file written by an incompatible version is discarded rather
+            // than trusted.
+            if lines.next() == Some(HEADER) {
+                for line in lines {
+                    if let [day, part, solver_version, input_sha256, answer] =
+                        line.splitn(5, '\t').collect::<Vec<_>>()[..]
+                    {
+                        let (Ok(day), Ok(part)) = (day.parse(), part.parse()) else {
+                            continue;
+                        };
+                        let key = CacheKey {
+                            day,
+                            part,
+                            solver_version: solver_version.to_string(),
+                            input_sha256: input_sha256.to_string(),
+                        };
+                        entries.insert(key, answer.to_string());
+                    }
+                }
+            }
+        }
+
+        Ok(Cache {
+            path,
+            entries,
+            dirty: false,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn get(&self, key: &CacheKey) -> Option<&str> {
+        self.entries.get(key).map(String::as_str)
+    }
+
+    /// Records an answer. Answers spanning lines cannot be stored and are
+    /// silently skipped.
+    pub fn insert(&mut self, key: CacheKey, answer: &str) {
+        if answer.contains(['\n', '\r']) {
+            return;
+        }
+
+        // Entries for older solver versions of the same day and part can
+        // never be hit again.
+        self.entries.retain(|k, _| {
+            k.day != key.day || k.part != key.part || k.solver_version == key.solver_version
+        });
+        self.entries.insert(key, answer.to_string());
+        self.dirty = true;
+    }
+
+    /// Writes the cache back if anything was inserted since loading.
+    pub fn save(&mut self) -> GenResult<()> {
+        if !self.dirty {
+            return Ok(());
+        }
+
+        let mut rows: Vec<_> = self.entries.iter().collect();
+        rows.sort_by(|(a, _), (b, _)| {
+            (a.day, a.part, &a.input_sha256).cmp(&(b.day, b.part, &b.input_sha256))
+        });
+
+        let mut out = format!("{}\n", HEADER);
+        for (key, answer) in rows {
+            out.push_str(&format!(
+                "{}\t{}\t{}\t{}\t{}\n",
+                key.day, key.part, key.solver_version, key.input_sha256, answer
+            ));
+        }
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = self.path.with_extension("tmp");
+        fs::write(&tmp, out).map_err(|e| Error::from(e).with_path(&tmp))?;
+        fs::rename(&tmp, &self.path).map_err(|e| Error::from(e).with_path(&self.path))?;
+
+        self.dirty = false;
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+//! `aoc calibrate`: day 1's calibration sum under chosen reading rules.
+
+use crate::args::Args;
+use aoc::days::find_day;
+use aoc_core::{lines, read_input, GenResult};
+use day1::{Calibration, Dictionary, DigitPolicy, Rules, Tally};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+
+pub fn cmd_calibrate(mut args: Args) -> GenResult<()> {
+    let input = args.value("input")?.map(PathBuf::from);
+    let languages = args.value("words")?.unwrap_or_else(|| "en".to_string());
+    let digits = args.parsed::<DigitPolicy>("digits")?.unwrap_or_default();
+    let rules = args.parsed::<Rules>("rules")?.unwrap_or_default();
+    let explain = args.flag("explain");
+    let color = args.flag("color");
+    let lenient = args.flag("lenient");
+    let threads = match args.parsed::<usize>("threads")? {
+        Some(n) => n,
+        None => thread::available_parallelism().map_or(1, |n| n.get()),
+    };
+    let mut files = vec![];
+    while let Some(path) = args.value("dict")? {
+        files.push(PathBuf::from(path));
+    }
+    args.finish()?;
+
+    let mut dictionary = Dictionary::default();
+    if languages != "none" {
+        for language in languages.split(',') {
+            dictionary.extend(&Dictionary::builtin(language.trim())?)?;
+        }
+    }
+    for path in &files {
+        dictionary.extend(&Dictionary::load(path)?)?;
+    }
+
+    let input = input.unwrap_or(find_day(1)?.default_input());
+    let calibration = Calibration::with_rules(&dictionary, digits, rules);
+    let tally = if explain {
+        let text = read_input(&input)?;
+        explain_lines(&text, &calibration, color, lenient).map_err(|e| e.with_path(&input))?
+    } else if input == Path::new("-") {
+        calibration.tally_reader(io::stdin().lock(), lenient)?
+    } else {
+        calibration.tally_file(&input, threads, lenient)?
+    };
+
+    println!("{}", tally.sum);
+    if lenient {
+        eprint!("{}", tally.report());
+    }
+    Ok(())
+}
+
+/// Prints how each line's value was found, then returns the sum.
+fn explain_lines(
+    text: &str,
+    calibration: &Calibration,
+    color: bool,
+    lenient: bool,
+) -> GenResult<Tally> {
+    let mut out = io::stdout().lock();
+    let mut tally = Tally::default();
+    for line in lines(text) {
+        let explanation = calibration.explain(&line);
+        if let Ok(explanation) = &explanation {
+            writeln!(out, "{}", explanation.render(line.text, color))?;
+        }
+        tally.add(explanation.map(|e| e.value), lenient)?;
+    }
+
+    Ok(tally)
+}
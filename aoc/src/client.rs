@@ -0,0 +1,197 @@
+//! Downloading puzzle inputs and submitting answers.
+//!
+//! The session token and server come from a small `key = value` config
+//! file, so the client can be pointed at a local stand-in server:
+//!
+//! ```text
+//! session = 53616c7465645f5f...
+//! base_url = https://adventofcode.com
+//! year = 2023
+//! ```
+
+use crate::http::{form_encode, Request, Response};
+use crate::submissions::{Submission, Submissions, Verdict};
+use aoc_core::{Error, GenResult};
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const DEFAULT_YEAR: u32 = 2023;
+
+const USER_AGENT: &str = concat!("aoc-runner/", env!("CARGO_PKG_VERSION"));
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub session: String,
+    pub base_url: String,
+    pub year: u32,
+}
+
+impl Config {
+    /// Reads a config file. Blank lines and `#` comments are ignored; only
+    /// `session` is required.
+    pub fn load(path: &Path) -> GenResult<Self> {
+        let contents = fs::read_to_string(path).map_err(|e| Error::from(e).with_path(path))?;
+
+        let mut session = None;
+        let mut base_url = DEFAULT_BASE_URL.to_string();
+        let mut year = DEFAULT_YEAR;
+        for (i, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .map(|(k, v)| (k.trim(), v.trim()))
+                .ok_or(format!(
+                    "{}:{}: expected '<key> = <value>'",
+                    path.display(),
+                    i + 1
+                ))?;
+            match key {
+                "session" => session = Some(value.to_string()),
+                "base_url" => base_url = value.trim_end_matches('/').to_string(),
+                "year" => year = value.parse()?,
+                _ => {
+                    return Err(format!(
+                        "{}:{}: unknown key '{}', expected session, base_url or year",
+                        path.display(),
+                        i + 1,
+                        key
+                    )
+                    .into())
+                }
+            }
+        }
+
+        let session = session.ok_or(format!("{} does not set a session", path.display()))?;
+        Ok(Config {
+            session,
+            base_url,
+            year,
+        })
+    }
+}
+
+pub struct Client {
+    config: Config,
+}
+
+impl Client {
+    pub fn new(config: Config) -> Self {
+        Client { config }
+    }
+
+    pub fn fetch_input(&self, day: u32) -> GenResult<String> {
+        let url = format!("{}/input", self.day_url(day));
+        let response = self.request("GET", &url, None).send()?;
+        expect_ok(&response, &url)?;
+        Ok(response.body)
+    }
+
+    /// Submits `answer` unless `log` shows it cannot be right, and records
+    /// the server's verdict.
+    pub fn submit(
+        &self,
+        log: &mut Submissions,
+        day: u32,
+        part: u32,
+        answer: &str,
+    ) -> GenResult<Verdict> {
+        if let Some(reason) = log.refusal(day, part, answer) {
+            return Err(format!("Not submitting: {}", reason).into());
+        }
+
+        let url = format!("{}/answer", self.day_url(day));
+        let form = format!("level={}&answer={}", part, form_encode(answer));
+        let response = self.request("POST", &url, Some(form)).send()?;
+        expect_ok(&response, &url)?;
+        let verdict = parse_verdict(&response.body)?;
+
+        log.record(Submission {
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict,
+            unix_time: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+        })?;
+        Ok(verdict)
+    }
+
+    fn day_url(&self, day: u32) -> String {
+        format!("{}/{}/day/{}", self.config.base_url, self.config.year, day)
+    }
+
+    fn request<'a>(&self, method: &'a str, url: &'a str, form: Option<String>) -> Request<'a> {
+        Request {
+            method,
+            url,
+            headers: vec![
+                ("Cookie", format!("session={}", self.config.session)),
+                ("User-Agent", USER_AGENT.to_string()),
+            ],
+            form,
+        }
+    }
+}
+
+fn expect_ok(response: &Response, url: &str) -> GenResult<()> {
+    if response.status == 200 {
+        return Ok(());
+    }
+    Err(format!(
+        "{} returned {}: {}",
+        url,
+        response.status,
+        response.body.lines().next().unwrap_or("").trim()
+    )
+    .into())
+}
+
+/// Reads the verdict out of the server's answer page. Responses that say
+/// nothing about the answer itself, like being asked to wait, are errors so
+/// that they are not recorded.
+fn parse_verdict(body: &str) -> GenResult<Verdict> {
+    if body.contains("That's the right answer") {
+        Ok(Verdict::Accepted)
+    } else if body.contains("That's not the right answer") {
+        Ok(if body.contains("too high") {
+            Verdict::TooHigh
+        } else if body.contains("too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        })
+    } else if body.contains("You gave an answer too recently") {
+        Err(format!("Rate limited: {}", article_text(body)).into())
+    } else if body.contains("You don't seem to be solving the right level") {
+        Err("The server is not expecting this part; is it already solved or still locked?".into())
+    } else {
+        Err(format!("Unrecognised response: {}", article_text(body)).into())
+    }
+}
+
+/// The page's `<article>` (or the whole body) with tags stripped.
+fn article_text(body: &str) -> String {
+    let article = body
+        .split_once("<article>")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(body, |(article, _)| article);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,223 @@
+//! `aoc cubes`: questions about day 2's game log under a chosen bag.
+
+use crate::args::Args;
+use aoc::days::find_day;
+use aoc::output::{csv_field, json_string, Format};
+use aoc_core::{read_input, GenResult};
+use day2::{
+    check_ids, describe, elf_bag, id_sum, load_bag, log_likelihood, most_likely_bag, parse_bag,
+    parse_games, query, smallest_bag, sort_summaries, summarize, Column, Cubes, Game, Palette,
+    ParseOptions, Summary,
+};
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+pub fn cmd_cubes(mut args: Args) -> GenResult<()> {
+    let input = args.value("input")?.map(PathBuf::from);
+    let options = ParseOptions {
+        palette: args.parsed::<Palette>("colors")?.unwrap_or_default(),
+        merge: args.flag("merge"),
+    };
+    let bag = args.value("bag")?;
+    let bag_file = args.value("bag-file")?.map(PathBuf::from);
+    let ids = args.value("games")?;
+    let max_cubes = args.parsed::<usize>("max-cubes")?.unwrap_or(1000);
+    let format = args.parsed::<Format>("format")?.unwrap_or(Format::Text);
+    let sort = args.parsed::<Column>("sort")?.unwrap_or(Column::Id);
+    let descending = args.flag("desc");
+    let Some(command) = args.positional() else {
+        return Err("Missing cubes command, expected query, bag, infer or report".into());
+    };
+    args.finish()?;
+
+    let bag = match (bag, bag_file) {
+        (Some(_), Some(_)) => return Err("Give --bag or --bag-file, not both".into()),
+        (Some(spec), None) => Some(parse_bag(&spec)?),
+        (None, Some(path)) => Some(load_bag(&path)?),
+        (None, None) => None,
+    };
+
+    let input = input.unwrap_or(find_day(2)?.default_input());
+    let games = parse_games(&read_input(&input)?, &options).map_err(|e| e.with_path(&input))?;
+    for issue in check_ids(&games) {
+        eprintln!("warning: {}: {}", input.display(), issue);
+    }
+    let colors = options.palette.colors(&games);
+
+    match command.as_str() {
+        "query" => print_query(&games, &bag.unwrap_or_else(elf_bag), &colors),
+        "bag" => {
+            let chosen = match ids {
+                Some(ids) => select_games(&games, &ids)?,
+                None => games.iter().collect(),
+            };
+            println!("{}", describe(&smallest_bag(chosen), &colors));
+            Ok(())
+        }
+        "infer" => {
+            let estimate = most_likely_bag(&games, &colors, max_cubes)?;
+            println!(
+                "most likely bag: {} (log-likelihood {:.3})",
+                describe(&estimate.bag, &colors),
+                estimate.log_likelihood
+            );
+            if estimate.at_limit {
+                println!(
+                    "  still rising at {} cubes; a larger --max-cubes may find a likelier bag",
+                    max_cubes
+                );
+            }
+            print_likelihoods(&games, &bag.unwrap_or(estimate.bag), &colors);
+            Ok(())
+        }
+        "report" => {
+            let mut rows = summarize(&games, &bag.unwrap_or_else(elf_bag), &colors)?;
+            sort_summaries(&mut rows, &sort, &colors, descending)?;
+            print!("{}", render_report(&rows, &colors, format));
+            Ok(())
+        }
+        _ => Err(format!(
+            "Unknown cubes command '{}', expected query, bag, infer or report",
+            command
+        )
+        .into()),
+    }
+}
+
+fn print_likelihoods(games: &[Game], bag: &Cubes, colors: &[String]) {
+    println!("under {}:", describe(bag, colors));
+    for game in games {
+        let ln = log_likelihood(&game.rounds, bag);
+        if ln == f64::NEG_INFINITY {
+            println!("  game {}: impossible", game.id);
+        } else {
+            println!(
+                "  game {}: likelihood {:.3e} (log {:.3})",
+                game.id,
+                ln.exp(),
+                ln
+            );
+        }
+    }
+}
+
+fn print_query(games: &[Game], bag: &Cubes, colors: &[String]) -> GenResult<()> {
+    let (feasible, infeasible) = query(games, bag);
+
+    let ids: Vec<String> = feasible.iter().map(|g| g.id.to_string()).collect();
+    let id_sum = id_sum(feasible.iter().copied())?;
+    println!("bag: {}", describe(bag, colors));
+    println!(
+        "feasible ({}, ID sum {}): {}",
+        feasible.len(),
+        id_sum,
+        ids.join(", ")
+    );
+    println!("infeasible ({}):", infeasible.len());
+    for (game, offense) in infeasible {
+        let over: Vec<String> = offense
+            .over
+            .iter()
+            .map(|(color, drawn, limit)| format!("{} {} (bag has {})", drawn, color, limit))
+            .collect();
+        println!(
+            "  game {}: round {} draws {}",
+            game.id,
+            offense.round + 1,
+            over.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// The games whose IDs are listed in `ids`, comma-separated.
+fn select_games<'a>(games: &'a [Game], ids: &str) -> GenResult<Vec<&'a Game>> {
+    let mut wanted = HashSet::new();
+    for id in ids.split(',') {
+        let id: usize = id
+            .trim()
+            .parse()
+            .map_err(|_| format!("Invalid game ID '{}' in --games", id.trim()))?;
+        if !games.iter().any(|g| g.id == id) {
+            return Err(format!("No game {} in the log", id).into());
+        }
+        wanted.insert(id);
+    }
+
+    Ok(games.iter().filter(|g| wanted.contains(&g.id)).collect())
+}
+
+/// The report as an aligned table, JSON lines or CSV with a header.
+fn render_report(rows: &[Summary], colors: &[String], format: Format) -> String {
+    let fields = |row: &Summary| -> Vec<String> {
+        let mut fields = vec![row.id.to_string(), row.rounds.to_string()];
+        fields.extend(
+            colors
+                .iter()
+                .map(|color| row.most.get(color).copied().unwrap_or(0).to_string()),
+        );
+        fields.push(row.power.to_string());
+        fields.push(row.feasible.to_string());
+        fields
+    };
+    let mut header = vec!["id".to_string(), "rounds".to_string()];
+    header.extend(colors.iter().cloned());
+    header.extend(["power".to_string(), "feasible".to_string()]);
+
+    let mut out = String::new();
+    match format {
+        Format::Text => {
+            let table: Vec<Vec<String>> = std::iter::once(header)
+                .chain(rows.iter().map(fields))
+                .collect();
+            let widths: Vec<usize> = (0..table[0].len())
+                .map(|i| {
+                    table
+                        .iter()
+                        .map(|r| r[i].chars().count())
+                        .max()
+                        .unwrap_or(0)
+                })
+                .collect();
+            for row in &table {
+                let cells: Vec<String> = row
+                    .iter()
+                    .zip(&widths)
+                    .map(|(cell, &w)| format!("{:>w$}", cell, w = w))
+                    .collect();
+                out += &cells.join("  ");
+                out.push('\n');
+            }
+        }
+        Format::JsonLines => {
+            for row in rows {
+                let most: Vec<String> = colors
+                    .iter()
+                    .map(|color| {
+                        let n = row.most.get(color).copied().unwrap_or(0);
+                        format!("{}:{}", json_string(color), n)
+                    })
+                    .collect();
+                out += &format!(
+                    "{{\"id\":{},\"rounds\":{},\"most\":{{{}}},\"power\":{},\"feasible\":{}}}\n",
+                    row.id,
+                    row.rounds,
+                    most.join(","),
+                    row.power,
+                    row.feasible
+                );
+            }
+        }
+        Format::Csv => {
+            let header: Vec<String> = header.iter().map(|h| csv_field(h)).collect();
+            out += &header.join(",");
+            out.push('\n');
+            for row in rows {
+                out += &fields(row).join(",");
+                out.push('\n');
+            }
+        }
+    }
+
+    out
+}
@@ -0,0 +1,83 @@
+use aoc_core::{DynSolution, GenResult};
+use std::path::PathBuf;
+
+// Generated by build.rs: a fingerprint of each day's sources.
+include!(concat!(env!("OUT_DIR"), "/solver_versions.rs"));
+
+pub struct Day {
+    pub number: u32,
+    pub solution: &'static dyn DynSolution,
+}
+
+impl Day {
+    /// The directory holding the day's sources and fixtures.
+    pub fn dir(&self) -> PathBuf {
+        workspace_root().join(format!("day{}", self.number))
+    }
+
+    /// Changes whenever the day's sources (or aoc-core) change.
+    pub fn solver_version(&self) -> &'static str {
+        SOLVER_VERSIONS
+            .iter()
+            .find(|(number, _)| *number == self.number)
+            .map(|(_, version)| *version)
+            .unwrap_or("unknown")
+    }
+
+    /// The puzzle input committed alongside the day's sources.
+    pub fn default_input(&self) -> PathBuf {
+        self.dir().join("input.txt")
+    }
+}
+
+pub const DAYS: &[Day] = &[
+    Day {
+        number: 1,
+        solution: &day1::Day1,
+    },
+    Day {
+        number: 2,
+        solution: &day2::Day2,
+    },
+    Day {
+        number: 3,
+        solution: &day3::Day3,
+    },
+    Day {
+        number: 4,
+        solution: &day4::Day4,
+    },
+    Day {
+        number: 5,
+        solution: &day5::Day5,
+    },
+    Day {
+        number: 6,
+        solution: &day6::Day6,
+    },
+    Day {
+        number: 7,
+        solution: &day7::Day7,
+    },
+    Day {
+        number: 8,
+        solution: &day8::Day8,
+    },
+    Day {
+        number: 9,
+        solution: &day9::Day9,
+    },
+];
+
+pub fn find_day(number: u32) -> GenResult<&'static Day> {
+    DAYS.iter()
+        .find(|day| day.number == number)
+        .ok_or_else(|| format!("Day {} is not implemented", number).into())
+}
+
+pub fn workspace_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .map(PathBuf::from)
+        .unwrap_or_default()
+}
@@ -0,0 +1,46 @@
+//! `aoc gears`: day 3's gears under chosen rules.
+
+use crate::args::Args;
+use aoc::days::find_day;
+use aoc_core::{read_input, GenResult, Solution};
+use day3::{total_value, Aggregate, Count, Day3, GearRules};
+use std::path::PathBuf;
+
+pub fn cmd_gears(mut args: Args) -> GenResult<()> {
+    let input = args.value("input")?.map(PathBuf::from);
+    let defaults = GearRules::default();
+    let symbols = match args.value("symbols")? {
+        Some(any) if any == "any" => None,
+        Some(symbols) => Some(symbols.chars().collect()),
+        None => defaults.symbols,
+    };
+    let rules = GearRules {
+        symbols,
+        count: args.parsed::<Count>("count")?.unwrap_or(defaults.count),
+        aggregate: args
+            .parsed::<Aggregate>("aggregate")?
+            .unwrap_or(defaults.aggregate),
+    };
+    let list = args.flag("list");
+    args.finish()?;
+
+    let input = input.unwrap_or(find_day(3)?.default_input());
+    let schematic = Day3::parse(&read_input(&input)?).map_err(|e| e.with_path(&input))?;
+    let gears = schematic.gears(&rules)?;
+
+    if list {
+        for gear in &gears {
+            let numbers: Vec<String> = gear.numbers.iter().map(i32::to_string).collect();
+            println!(
+                "{}:{} {} [{}] -> {}",
+                gear.pos.row + 1,
+                gear.pos.col + 1,
+                gear.symbol,
+                numbers.join(", "),
+                gear.value
+            );
+        }
+    }
+    println!("{}", total_value(&gears)?);
+    Ok(())
+}
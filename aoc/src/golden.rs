@@ -0,0 +1,93 @@
+//! Golden answers: every day keeps an `answers.txt` manifest recording the
+//! expected answer for each fixture and part.
+
+use crate::days::Day;
+use aoc_core::{read_input, GenResult};
+use std::fs::read_to_string;
+
+pub const MANIFEST: &str = "answers.txt";
+
+/// Stands in for an answer that is not known yet; such entries are skipped.
+pub const PENDING: &str = "?";
+
+#[derive(Debug)]
+pub struct Expected {
+    pub fixture: String,
+    pub part: u32,
+    pub answer: String,
+}
+
+/// Reads the day's manifest. Blank lines and `#` comments are ignored.
+pub fn load_manifest(day: &Day) -> GenResult<Vec<Expected>> {
+    let path = day.dir().join(MANIFEST);
+    let contents =
+        read_to_string(&path).map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(
+            |(i, line)| match line.split_whitespace().collect::<Vec<_>>()[..] {
+                [fixture, part, answer] => Ok(Expected {
+                    fixture: fixture.to_string(),
+                    part: part.parse::<u32>()?,
+                    answer: answer.to_string(),
+                }),
+                _ => Err(format!(
+                    "{}:{}: expected '<fixture> <part> <answer>'",
+                    path.display(),
+                    i + 1
+                )
+                .into()),
+            },
+        )
+        .collect()
+}
+
+/// Lists the `.txt` fixtures next to the day's sources, excluding the manifest.
+pub fn fixtures(day: &Day) -> GenResult<Vec<String>> {
+    let mut names = vec![];
+    for entry in day.dir().read_dir()? {
+        let name = entry?.file_name().to_string_lossy().to_string();
+        if name.ends_with(".txt") && name != MANIFEST {
+            names.push(name);
+        }
+    }
+
+    names.sort();
+    Ok(names)
+}
+
+/// Runs every manifest entry for `day` and describes each mismatch or error.
+pub fn check_day(day: &Day) -> GenResult<Vec<String>> {
+    let mut failures = vec![];
+
+    for expected in load_manifest(day)? {
+        if expected.answer == PENDING {
+            continue;
+        }
+
+        let path = day.dir().join(&expected.fixture);
+        let input = read_input(&path)?;
+        let actual = day
+            .solution
+            .parse(&input)
+            .and_then(|parsed| day.solution.solve(parsed.as_ref(), expected.part))
+            .map_err(|e| e.with_path(&path));
+
+        match actual {
+            Ok(answer) if answer == expected.answer => {}
+            Ok(answer) => failures.push(format!(
+                "day {} {} part {}: expected {}, got {}",
+                day.number, expected.fixture, expected.part, expected.answer, answer
+            )),
+            Err(e) => failures.push(format!(
+                "day {} {} part {}: expected {}, got error: {}",
+                day.number, expected.fixture, expected.part, expected.answer, e
+            )),
+        }
+    }
+
+    Ok(failures)
+}
@@ -0,0 +1,155 @@
+//! Just enough HTTP for the puzzle client. `http://` URLs are spoken to
+//! directly, which is what the local stand-in server in tests uses;
+//! `https://` requests are handed to `curl`.
+
+use aoc_core::GenResult;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+pub struct Request<'a> {
+    pub method: &'a str,
+    pub url: &'a str,
+    pub headers: Vec<(&'a str, String)>,
+    /// Sent form-encoded when present.
+    pub form: Option<String>,
+}
+
+impl Request<'_> {
+    pub fn send(&self) -> GenResult<Response> {
+        if let Some(rest) = self.url.strip_prefix("http://") {
+            self.send_plain(rest)
+        } else if self.url.starts_with("https://") {
+            self.send_curl()
+        } else {
+            Err(format!(
+                "Unsupported URL '{}', expected http:// or https://",
+                self.url
+            )
+            .into())
+        }
+    }
+
+    fn send_plain(&self, rest: &str) -> GenResult<Response> {
+        let (authority, path) = match rest.find('/') {
+            Some(i) => rest.split_at(i),
+            None => (rest, "/"),
+        };
+        let address = if authority.contains(':') {
+            authority.to_string()
+        } else {
+            format!("{}:80", authority)
+        };
+
+        let mut stream = TcpStream::connect(&address)
+            .map_err(|e| format!("Could not connect to {}: {}", address, e))?;
+        stream.set_read_timeout(Some(TIMEOUT))?;
+
+        let mut request = format!(
+            "{} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\n",
+            self.method, path, authority
+        );
+        for (name, value) in &self.headers {
+            request.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        if let Some(form) = &self.form {
+            request.push_str("Content-Type: application/x-www-form-urlencoded\r\n");
+            request.push_str(&format!("Content-Length: {}\r\n\r\n{}", form.len(), form));
+        } else {
+            request.push_str("\r\n");
+        }
+        stream.write_all(request.as_bytes())?;
+
+        let mut raw = vec![];
+        stream.read_to_end(&mut raw)?;
+        parse_response(&String::from_utf8_lossy(&raw))
+    }
+
+    /// Everything, including the session cookie, goes to curl through a
+    /// config on stdin so that it never shows up in the process list.
+    fn send_curl(&self) -> GenResult<Response> {
+        let mut config = format!(
+            "url = {}\nrequest = {}\nsilent\nshow-error\nmax-time = {}\nwrite-out = \"\\n%{{http_code}}\"\n",
+            quote(self.url),
+            quote(self.method),
+            TIMEOUT.as_secs()
+        );
+        for (name, value) in &self.headers {
+            config.push_str(&format!(
+                "header = {}\n",
+                quote(&format!("{}: {}", name, value))
+            ));
+        }
+        if let Some(form) = &self.form {
+            config.push_str(&format!("data = {}\n", quote(form)));
+        }
+
+        let mut child = Command::new("curl")
+            .args(["--config", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Could not run curl: {}", e))?;
+        child.stdin.take().unwrap().write_all(config.as_bytes())?;
+        let output = child.wait_with_output()?;
+
+        if !output.status.success() {
+            return Err(format!(
+                "curl failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )
+            .into());
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let (body, status) = stdout
+            .rsplit_once('\n')
+            .ok_or("curl did not report a status code")?;
+        Ok(Response {
+            status: status.trim().parse()?,
+            body: body.to_string(),
+        })
+    }
+}
+
+fn parse_response(raw: &str) -> GenResult<Response> {
+    let (head, body) = raw
+        .split_once("\r\n\r\n")
+        .ok_or("Malformed HTTP response: no end of headers")?;
+    let status = head
+        .lines()
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .ok_or("Malformed HTTP response: no status line")?;
+
+    Ok(Response {
+        status: status.parse()?,
+        body: body.to_string(),
+    })
+}
+
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Percent-encodes `s` for an `application/x-www-form-urlencoded` body.
+pub fn form_encode(s: &str) -> String {
+    s.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
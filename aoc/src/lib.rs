@@ -0,0 +1,14 @@
+//! The runner's registry of days and the tooling built around it.
+
+pub mod alloc;
+pub mod bench;
+pub mod cache;
+pub mod client;
+pub mod days;
+pub mod golden;
+pub mod http;
+pub mod output;
+pub mod run;
+pub mod scaffold;
+pub mod sha256;
+pub mod submissions;
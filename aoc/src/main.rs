@@ -0,0 +1,338 @@
+mod args;
+mod calibrate;
+mod cubes;
+mod gears;
+
+use aoc::alloc::CountingAlloc;
+use aoc::bench;
+use aoc::cache::Cache;
+use aoc::client::{Client, Config};
+use aoc::days::{find_day, workspace_root, Day, DAYS};
+use aoc::output::{Format, Printer};
+use aoc::run::solve_day;
+use aoc::scaffold;
+use aoc::submissions::{Submissions, Verdict};
+use aoc_core::GenResult;
+use args::Args;
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+const USAGE: &str = "\
+usage: aoc <command> [options]
+
+commands:
+  run --day N [--part P] [--input PATH] [--format FMT] [--no-cache]
+      Run one day. Both parts run unless --part is given. PATH defaults to
+      the day's input.txt; `-` reads the input from stdin. Only answers are
+      printed: day 2's game IDs, for one, are checked by `cubes`, not here.
+  all [--format FMT] [--no-cache]
+      Run every day against its own input.txt, in order.
+  bench [--day N] [--iterations K] [--output PATH] [--baseline PATH]
+        [--threshold PCT]
+      Time each day's parse, part 1 and part 2 over K runs (default 20) and
+      report min/median/p95 and allocations per run. Results are written to
+      PATH (default target/aoc-bench.tsv). With --baseline, stages whose
+      median slowed by more than PCT percent (default 10) or that allocate
+      more than in the baseline file are reported and the command fails.
+  new-day N
+      Create dayN/ with a stub solver answering 0, empty input.txt and
+      test.txt fixtures and an answers.txt whose answers are `?` (not
+      checked until filled in), then register the day with the runner.
+  fetch --day N [--force] [--config PATH]
+      Download the day's input into its input.txt. An existing non-empty
+      input is only replaced with --force.
+  submit --day N --part P [--answer A] [--input PATH] [--config PATH]
+      Submit an answer, solving the part first when --answer is not given.
+      Verdicts are recorded in submissions.tsv; answers that are already
+      known to be wrong, or parts already accepted, are not submitted again.
+  calibrate [--input PATH] [--words LANGS] [--dict PATH]... [--digits POLICY]
+            [--rules RULES] [--explain [--color]] [--threads N] [--lenient]
+      Day 1's calibration sum, reading spelled-out digits from the built-in
+      word lists LANGS (comma-separated: en, fr, de, es; default en; `none`
+      for digits only) and any number of dictionary files of `<word> <value>`
+      lines. POLICY decides which characters are digits: `ascii` (default),
+      `unicode` (decimal digits of any script, e.g. '٣' is 3) or `reject`
+      (ASCII only, and any other numeral is an error). RULES combines the
+      digits: `aoc` (default; first and last digit), `no-overlap` (words may
+      not share letters), `first2-last2` or `numbers` (runs of English
+      number words such as `twenty three` are one number), or a spec like
+      `first=2,last=1,words=numbers,overlap=yes`. --explain lists, for
+      every line, each digit and word found with its byte span and which
+      were taken as first and last; --color highlights them in the line.
+      Otherwise the input is streamed a line at a time, and files are split
+      into N chunks summed in parallel (default: one per CPU). A line with
+      no value ends the run unless --lenient is given, which skips such
+      lines and lists them, with the reason, on stderr after the sum.
+  cubes query [--input PATH] [--colors COLORS] [--merge]
+              [--bag BAG | --bag-file PATH]
+  cubes bag [--input PATH] [--colors COLORS] [--merge] [--games IDS]
+  cubes infer [--input PATH] [--colors COLORS] [--merge] [--max-cubes N]
+              [--bag BAG | --bag-file PATH]
+  cubes report [--input PATH] [--colors COLORS] [--merge] [--format FMT]
+               [--sort COLUMN [--desc]] [--bag BAG | --bag-file PATH]
+      Day 2's game log under a bag. query lists the games the bag allows
+      and, for each of the others, the first round it cannot supply. BAG is
+      written like a round, e.g. `12 red, 13 green, 14 blue` (the default);
+      a bag file holds the same entries, one per line if preferred, with `#`
+      comments. bag prints the smallest bag allowing the games with the
+      comma-separated IDS (default: all of them). infer treats each round
+      as cubes drawn without replacement and put back afterwards, prints
+      the bag of at most N cubes (default 1000) under which the log is most
+      likely, and each game's likelihood under BAG (default: that bag).
+      report prints a row per game: its ID, number of rounds, the most of
+      each color drawn at once, its power and whether BAG allows it, sorted
+      by COLUMN (`id`, the default, `rounds`, a color, `power` or
+      `feasible`). COLORS is the colors the log may use, comma-separated
+      (default red,green,blue), or `open` for any. A color drawn twice in
+      one round is an error unless --merge is given, which adds the counts
+      up. Game IDs missing from 1, 2, 3, ... or out of order are reported
+      on stderr as warnings; `run` and `all` do not check them.
+  gears [--input PATH] [--symbols CHARS] [--count COUNT] [--aggregate AGG]
+        [--list]
+      Day 3's sum of gear values. A gear is one of the symbols CHARS
+      (default `*`; `any` for every symbol) touching COUNT numbers: `N`
+      exactly (default 2), `>=N` or `<=N`, and never none. Its value
+      combines those numbers by AGG: `product` (default), `sum` or `max`.
+      --list prints each gear's line:column, symbol, numbers and value
+      first.
+
+fetch and submit read the session token from aoc.conf in the workspace root
+(or --config), a file of `key = value` lines: `session` (required),
+`base_url` (default https://adventofcode.com) and `year` (default 2023).
+
+FMT is `text` (default), `jsonl` or `csv`. The structured formats emit one
+record per day and part with the answer, parse and solve times in
+nanoseconds, the input path and the SHA-256 of the input.
+
+Answers are cached in target/aoc-cache.tsv, keyed by day, part, a
+fingerprint of the day's sources and the SHA-256 of the input. Editing a
+solver invalidates its entries; --no-cache bypasses the cache entirely.
+";
+
+fn main() -> ExitCode {
+    match run_cli(Args::from_env()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run_cli(mut args: Args) -> GenResult<()> {
+    let Some(command) = args.positional() else {
+        eprint!("{}", USAGE);
+        return Err("Missing command".into());
+    };
+
+    match command.as_str() {
+        "run" => cmd_run(args),
+        "all" => cmd_all(args),
+        "bench" => cmd_bench(args),
+        "new-day" => cmd_new_day(args),
+        "calibrate" => calibrate::cmd_calibrate(args),
+        "cubes" => cubes::cmd_cubes(args),
+        "gears" => gears::cmd_gears(args),
+        "fetch" => cmd_fetch(args),
+        "submit" => cmd_submit(args),
+        "help" => {
+            print!("{}", USAGE);
+            Ok(())
+        }
+        _ => Err(format!("Unknown command '{}', see `aoc help`", command).into()),
+    }
+}
+
+fn cmd_run(mut args: Args) -> GenResult<()> {
+    let day_num = args
+        .parsed::<u32>("day")?
+        .ok_or("Missing required option --day")?;
+    let part = args.parsed::<u32>("part")?;
+    let input = args.value("input")?.map(PathBuf::from);
+    let format = args.parsed::<Format>("format")?.unwrap_or(Format::Text);
+    let mut cache = open_cache(args.flag("no-cache"))?;
+    args.finish()?;
+
+    let day = find_day(day_num)?;
+    let parts = match part {
+        Some(p) => vec![p],
+        None => vec![1, 2],
+    };
+
+    let input = input.unwrap_or_else(|| day.default_input());
+    let mut printer = Printer::new(format);
+    for record in solve_day(day, &parts, &input, cache.as_mut())? {
+        printer.print(&record)?;
+    }
+
+    save_cache(cache)
+}
+
+fn cmd_all(mut args: Args) -> GenResult<()> {
+    let format = args.parsed::<Format>("format")?.unwrap_or(Format::Text);
+    let mut cache = open_cache(args.flag("no-cache"))?;
+    args.finish()?;
+
+    let mut printer = Printer::new(format);
+    for day in DAYS {
+        if format == Format::Text {
+            println!("day {}", day.number);
+        }
+        let records = solve_day(day, &[1, 2], &day.default_input(), cache.as_mut());
+        // Keep whatever was solved so far even if a later day fails.
+        let records = records.inspect_err(|_| {
+            let _ = cache.as_mut().map(Cache::save);
+        })?;
+        for record in records {
+            printer.print(&record)?;
+        }
+    }
+
+    save_cache(cache)
+}
+
+fn open_cache(disabled: bool) -> GenResult<Option<Cache>> {
+    if disabled {
+        return Ok(None);
+    }
+    Ok(Some(Cache::load(
+        workspace_root().join("target").join("aoc-cache.tsv"),
+    )?))
+}
+
+fn save_cache(cache: Option<Cache>) -> GenResult<()> {
+    match cache {
+        Some(mut cache) => cache.save(),
+        None => Ok(()),
+    }
+}
+
+fn cmd_bench(mut args: Args) -> GenResult<()> {
+    let day_num = args.parsed::<u32>("day")?;
+    let iterations = args.parsed::<usize>("iterations")?.unwrap_or(20);
+    let output = args
+        .value("output")?
+        .map(PathBuf::from)
+        .unwrap_or_else(|| workspace_root().join("target").join("aoc-bench.tsv"));
+    let baseline = args.value("baseline")?.map(PathBuf::from);
+    let threshold = args.parsed::<f64>("threshold")?.unwrap_or(10.0);
+    args.finish()?;
+
+    let days: Vec<&Day> = match day_num {
+        Some(n) => vec![find_day(n)?],
+        None => DAYS.iter().collect(),
+    };
+
+    let mut results = vec![];
+    for day in days {
+        results.extend(bench::bench_day(day, &day.default_input(), iterations)?);
+    }
+
+    print!("{}", bench::format_table(&results));
+    bench::write_results(&output, &results)?;
+    println!("results written to {}", output.display());
+
+    if let Some(baseline) = baseline {
+        let previous = bench::read_results(&baseline)?;
+        let found = bench::regressions(&previous, &results, threshold / 100.0);
+        if !found.is_empty() {
+            for regression in &found {
+                println!("regression: {}", regression);
+            }
+            return Err(format!(
+                "{} regression(s) against {}",
+                found.len(),
+                baseline.display()
+            )
+            .into());
+        }
+        println!("no regressions against {}", baseline.display());
+    }
+
+    Ok(())
+}
+
+fn cmd_new_day(mut args: Args) -> GenResult<()> {
+    let number = args
+        .positional()
+        .ok_or("Missing day number, usage: aoc new-day N")?;
+    let number = number
+        .parse::<u32>()
+        .map_err(|_| format!("Invalid day number '{}'", number))?;
+    args.finish()?;
+
+    for path in scaffold::new_day(&workspace_root(), number)? {
+        println!("wrote {}", path.display());
+    }
+    Ok(())
+}
+
+fn open_client(args: &mut Args) -> GenResult<Client> {
+    let path = args
+        .value("config")?
+        .map(PathBuf::from)
+        .unwrap_or_else(|| workspace_root().join("aoc.conf"));
+    Ok(Client::new(Config::load(&path)?))
+}
+
+fn cmd_fetch(mut args: Args) -> GenResult<()> {
+    let day_num = args
+        .parsed::<u32>("day")?
+        .ok_or("Missing required option --day")?;
+    let force = args.flag("force");
+    let client = open_client(&mut args)?;
+    args.finish()?;
+
+    let path = find_day(day_num)?.default_input();
+    if !force && fs::metadata(&path).is_ok_and(|m| m.len() > 0) {
+        return Err(format!(
+            "{} already exists, use --force to replace it",
+            path.display()
+        )
+        .into());
+    }
+
+    let input = client.fetch_input(day_num)?;
+    fs::write(&path, input).map_err(|e| aoc_core::Error::from(e).with_path(&path))?;
+    println!("wrote {}", path.display());
+    Ok(())
+}
+
+fn cmd_submit(mut args: Args) -> GenResult<()> {
+    let day_num = args
+        .parsed::<u32>("day")?
+        .ok_or("Missing required option --day")?;
+    let part = args
+        .parsed::<u32>("part")?
+        .ok_or("Missing required option --part")?;
+    let answer = args.value("answer")?;
+    let input = args.value("input")?.map(PathBuf::from);
+    let client = open_client(&mut args)?;
+    args.finish()?;
+
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let day = find_day(day_num)?;
+            let input = input.unwrap_or_else(|| day.default_input());
+            let mut cache = open_cache(false)?;
+            let record = solve_day(day, &[part], &input, cache.as_mut())?.remove(0);
+            save_cache(cache)?;
+            record.answer
+        }
+    };
+
+    let mut log = Submissions::load(workspace_root().join("submissions.tsv"))?;
+    let verdict = client.submit(&mut log, day_num, part, &answer)?;
+    println!("day {} part {}: {} -> {}", day_num, part, answer, verdict);
+
+    if verdict == Verdict::Accepted {
+        Ok(())
+    } else {
+        Err(format!("{} was not accepted", answer).into())
+    }
+}
@@ -0,0 +1,122 @@
+//! Rendering of answers for people (`text`) and for tooling (`jsonl`, `csv`).
+
+use aoc_core::{Error, GenResult};
+use std::io::{self, Write};
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    Text,
+    JsonLines,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        match s {
+            "text" => Ok(Format::Text),
+            "jsonl" => Ok(Format::JsonLines),
+            "csv" => Ok(Format::Csv),
+            _ => Err(format!("Unknown format '{}', expected text, jsonl or csv", s).into()),
+        }
+    }
+}
+
+/// One answered part of one day.
+#[derive(Debug)]
+pub struct Record {
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    /// Time spent parsing the input, shared by both parts of a run.
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+    /// The answer came from the answer cache; both times are zero.
+    pub cached: bool,
+    pub input: String,
+    pub input_sha256: String,
+}
+
+const CSV_HEADER: &str = "day,part,answer,parse_ns,solve_ns,cached,input,input_sha256";
+
+/// Prints records in the chosen format, emitting the CSV header once.
+pub struct Printer {
+    format: Format,
+    header_written: bool,
+}
+
+impl Printer {
+    pub fn new(format: Format) -> Self {
+        Printer {
+            format,
+            header_written: false,
+        }
+    }
+
+    pub fn print(&mut self, record: &Record) -> GenResult<()> {
+        let mut out = io::stdout().lock();
+        if self.format == Format::Csv && !self.header_written {
+            writeln!(out, "{}", CSV_HEADER)?;
+            self.header_written = true;
+        }
+        writeln!(out, "{}", render(self.format, record))?;
+        Ok(())
+    }
+}
+
+pub fn render(format: Format, r: &Record) -> String {
+    match format {
+        Format::Text => format!("part {}: {}", r.part, r.answer),
+        Format::JsonLines => format!(
+            "{{\"day\":{},\"part\":{},\"answer\":{},\"parse_ns\":{},\"solve_ns\":{},\"cached\":{},\"input\":{},\"input_sha256\":{}}}",
+            r.day,
+            r.part,
+            json_string(&r.answer),
+            r.parse_time.as_nanos(),
+            r.solve_time.as_nanos(),
+            r.cached,
+            json_string(&r.input),
+            json_string(&r.input_sha256)
+        ),
+        Format::Csv => [
+            r.day.to_string(),
+            r.part.to_string(),
+            csv_field(&r.answer),
+            r.parse_time.as_nanos().to_string(),
+            r.solve_time.as_nanos().to_string(),
+            r.cached.to_string(),
+            csv_field(&r.input),
+            r.input_sha256.clone(),
+        ]
+        .join(","),
+    }
+}
+
+pub fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+pub fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
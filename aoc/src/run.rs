@@ -0,0 +1,76 @@
+//! Solving a day end to end: read the input, parse it once, answer parts.
+
+use crate::cache::{Cache, CacheKey};
+use crate::days::Day;
+use crate::output::Record;
+use crate::sha256::sha256_hex;
+use aoc_core::{read_input, GenResult};
+use std::any::Any;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Answers `parts` of `day` for the input at `input_path`. With a cache,
+/// parts answered before by the same solver version for the same input are
+/// returned without parsing or solving, and new answers are recorded.
+pub fn solve_day(
+    day: &Day,
+    parts: &[u32],
+    input_path: &Path,
+    mut cache: Option<&mut Cache>,
+) -> GenResult<Vec<Record>> {
+    let input = read_input(input_path)?;
+    let input_sha256 = sha256_hex(input.as_bytes());
+    let cache_key = |part| CacheKey {
+        day: day.number,
+        part,
+        solver_version: day.solver_version().to_string(),
+        input_sha256: input_sha256.clone(),
+    };
+
+    let mut parsed: Option<(Box<dyn Any>, Duration)> = None;
+    let mut records = vec![];
+    for &part in parts {
+        let record = |answer: String, parse_time, solve_time, cached| Record {
+            day: day.number,
+            part,
+            answer,
+            parse_time,
+            solve_time,
+            cached,
+            input: input_path.display().to_string(),
+            input_sha256: input_sha256.clone(),
+        };
+
+        if let Some(answer) = cache.as_deref().and_then(|c| c.get(&cache_key(part))) {
+            records.push(record(
+                answer.to_string(),
+                Duration::ZERO,
+                Duration::ZERO,
+                true,
+            ));
+            continue;
+        }
+
+        if parsed.is_none() {
+            let (result, parse_time) = timed(|| day.solution.parse(&input));
+            parsed = Some((result.map_err(|e| e.with_path(input_path))?, parse_time));
+        }
+        let (parsed, parse_time) = parsed.as_ref().unwrap();
+
+        let (answer, solve_time) = timed(|| day.solution.solve(parsed.as_ref(), part));
+        let answer = answer.map_err(|e| e.with_path(input_path))?;
+
+        if let Some(cache) = cache.as_deref_mut() {
+            cache.insert(cache_key(part), &answer);
+        }
+        records.push(record(answer, *parse_time, solve_time, false));
+    }
+
+    Ok(records)
+}
+
+fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let value = f();
+    (value, start.elapsed())
+}
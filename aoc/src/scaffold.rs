@@ -0,0 +1,202 @@
+//! Generating a new day: its crate, empty fixtures, a stub manifest and its
+//! registration with the runner.
+
+use crate::golden::{MANIFEST, PENDING};
+use aoc_core::{Error, GenResult};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const DAYS_TABLE: &str = "pub const DAYS: &[Day] = &[\n";
+
+/// Creates `day<number>` under the workspace `root` and registers it in the
+/// runner's manifest and day table. Returns every file created or changed.
+pub fn new_day(root: &Path, number: u32) -> GenResult<Vec<PathBuf>> {
+    let name = format!("day{}", number);
+    let dir = root.join(&name);
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()).into());
+    }
+
+    // Check both registration points before touching anything, so a failure
+    // leaves the tree as it was.
+    let manifest_path = root.join("aoc").join("Cargo.toml");
+    let table_path = root.join("aoc").join("src").join("days.rs");
+    let manifest = register_dependency(&read(&manifest_path)?, number)?;
+    let table = register_day(&read(&table_path)?, number)?;
+
+    fs::create_dir(&dir).map_err(|e| Error::from(e).with_path(&dir))?;
+    let files = [
+        ("Cargo.toml", crate_manifest(number)),
+        (&format!("{}.rs", name), solver_stub(number)),
+        ("input.txt", String::new()),
+        ("test.txt", String::new()),
+        (MANIFEST, answers_stub()),
+    ];
+
+    let mut written = vec![];
+    for (file, contents) in files {
+        let path = dir.join(file);
+        write(&path, &contents)?;
+        written.push(path);
+    }
+    write(&manifest_path, &manifest)?;
+    written.push(manifest_path);
+    write(&table_path, &table)?;
+    written.push(table_path);
+
+    Ok(written)
+}
+
+/// Adds `dayN = { path = "../dayN" }` to the runner's dependencies, keeping
+/// the day crates in numeric order.
+fn register_dependency(manifest: &str, number: u32) -> GenResult<String> {
+    let entry = format!("day{0} = {{ path = \"../day{0}\" }}\n", number);
+    let manifest = format!("{}\n", manifest.trim_end());
+    let mut lines: Vec<&str> = manifest.split_inclusive('\n').collect();
+
+    let deps = lines
+        .iter()
+        .position(|line| line.trim() == "[dependencies]")
+        .ok_or("aoc/Cargo.toml has no [dependencies] table")?;
+    let mut insert_at = deps + 1;
+    for (i, line) in lines.iter().enumerate().skip(deps + 1) {
+        if line.starts_with('[') {
+            break;
+        }
+        match day_dependency(line) {
+            Some(n) if n == number => {
+                return Err(format!("day{} is already a dependency of aoc", number).into())
+            }
+            Some(n) if n > number => break,
+            _ if !line.trim().is_empty() => insert_at = i + 1,
+            _ => {}
+        }
+    }
+
+    lines.insert(insert_at, &entry);
+    Ok(lines.concat())
+}
+
+fn day_dependency(line: &str) -> Option<u32> {
+    let (name, _) = line.split_once('=')?;
+    name.trim().strip_prefix("day")?.parse().ok()
+}
+
+/// Adds a `Day` entry to the `DAYS` table in `days.rs`, in numeric order.
+fn register_day(table: &str, number: u32) -> GenResult<String> {
+    let start = table
+        .find(DAYS_TABLE)
+        .ok_or("aoc/src/days.rs has no DAYS table")?
+        + DAYS_TABLE.len();
+    let end = start
+        + table[start..]
+            .find("];")
+            .ok_or("aoc/src/days.rs: DAYS table is not closed")?;
+
+    // Entries are rustfmt'd blocks starting with `    Day {`; insert before
+    // the first one with a larger number.
+    let mut insert_at = end;
+    let mut offset = start;
+    for line in table[start..end].split_inclusive('\n') {
+        if let Some(n) = line
+            .trim()
+            .strip_prefix("number:")
+            .and_then(|n| n.trim().trim_end_matches(',').parse::<u32>().ok())
+        {
+            if n == number {
+                return Err(format!("Day {} is already registered", number).into());
+            }
+            if n > number {
+                insert_at = table[..offset]
+                    .rfind("    Day {")
+                    .ok_or("aoc/src/days.rs: malformed DAYS entry")?;
+                break;
+            }
+        }
+        offset += line.len();
+    }
+
+    let entry = format!(
+        "    Day {{\n        number: {0},\n        solution: &day{0}::Day{0},\n    }},\n",
+        number
+    );
+    Ok(format!(
+        "{}{}{}",
+        &table[..insert_at],
+        entry,
+        &table[insert_at..]
+    ))
+}
+
+fn crate_manifest(number: u32) -> String {
+    format!(
+        "[package]
+name = \"day{0}\"
+version.workspace = true
+edition.workspace = true
+
+[lib]
+path = \"day{0}.rs\"
+
+[dependencies]
+aoc-core = {{ path = \"../aoc-core\" }}
+",
+        number
+    )
+}
+
+fn solver_stub(number: u32) -> String {
+    format!(
+        "use aoc_core::{{lines, GenResult, Line, Solution}};
+
+pub struct Day{0};
+
+// Both parts answer 0 until solved, so `aoc all` and `aoc bench` still run;
+// answers.txt leaves them unchecked until filled in.
+impl Solution for Day{0} {{
+    type Parsed = Vec<String>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> GenResult<Vec<String>> {{
+        lines(input).map(|line| parse_line(&line)).collect()
+    }}
+
+    fn part1(_parsed: &Vec<String>) -> GenResult<i64> {{
+        Ok(0)
+    }}
+
+    fn part2(_parsed: &Vec<String>) -> GenResult<i64> {{
+        Ok(0)
+    }}
+}}
+
+fn parse_line(line: &Line) -> GenResult<String> {{
+    Ok(line.text.to_string())
+}}
+",
+        number
+    )
+}
+
+fn answers_stub() -> String {
+    format!(
+        "# Expected answers for this day's fixtures: <fixture> <part> <answer>.
+# Parts that do not apply to a fixture are left out; `{0}` marks an answer
+# that is not known yet and is not checked.
+test.txt  1 {0}
+test.txt  2 {0}
+input.txt 1 {0}
+input.txt 2 {0}
+",
+        PENDING
+    )
+}
+
+fn read(path: &Path) -> GenResult<String> {
+    fs::read_to_string(path).map_err(|e| Error::from(e).with_path(path))
+}
+
+fn write(path: &Path, contents: &str) -> GenResult<()> {
+    fs::write(path, contents).map_err(|e| Error::from(e).with_path(path))
+}
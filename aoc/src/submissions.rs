@@ -0,0 +1,180 @@
+//! The record of every answer submitted and what the server made of it, used
+//! to avoid submitting an answer that is already known to be wrong.
+
+use aoc_core::{Error, GenResult};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+const HEADER: &str = "day\tpart\tanswer\tverdict\tunix_time";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Accepted,
+    Wrong,
+    TooHigh,
+    TooLow,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Verdict::Accepted => "accepted",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+        })
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        match s {
+            "accepted" => Ok(Verdict::Accepted),
+            "wrong" => Ok(Verdict::Wrong),
+            "too_high" => Ok(Verdict::TooHigh),
+            "too_low" => Ok(Verdict::TooLow),
+            _ => Err(format!("Unknown verdict '{}'", s).into()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Submission {
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub verdict: Verdict,
+    pub unix_time: u64,
+}
+
+pub struct Submissions {
+    path: PathBuf,
+    entries: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Loads the log at `path`; a missing file is an empty log.
+    pub fn load(path: impl Into<PathBuf>) -> GenResult<Self> {
+        let path = path.into();
+        let mut entries = vec![];
+
+        if path.exists() {
+            let contents =
+                fs::read_to_string(&path).map_err(|e| Error::from(e).with_path(&path))?;
+            let mut lines = contents.lines();
+            if lines.next() != Some(HEADER) {
+                return Err(format!("{} is not a submissions log", path.display()).into());
+            }
+
+            for line in lines.filter(|line| !line.is_empty()) {
+                match line.split('\t').collect::<Vec<_>>()[..] {
+                    [day, part, answer, verdict, unix_time] => entries.push(Submission {
+                        day: day.parse()?,
+                        part: part.parse()?,
+                        answer: answer.to_string(),
+                        verdict: verdict.parse()?,
+                        unix_time: unix_time.parse()?,
+                    }),
+                    _ => {
+                        return Err(
+                            format!("Malformed submission in {}: {}", path.display(), line).into(),
+                        )
+                    }
+                }
+            }
+        }
+
+        Ok(Submissions { path, entries })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn entries(&self) -> &[Submission] {
+        &self.entries
+    }
+
+    /// Explains why submitting `answer` would be pointless: the part is
+    /// already solved, this exact answer was rejected, an earlier
+    /// "too high"/"too low" hint already rules it out, or the log could not
+    /// record it.
+    pub fn refusal(&self, day: u32, part: u32, answer: &str) -> Option<String> {
+        if answer.contains(['\t', '\n', '\r']) {
+            return Some("answers containing tabs or line breaks cannot be recorded".to_string());
+        }
+
+        let value = answer.parse::<i128>().ok();
+
+        self.entries
+            .iter()
+            .filter(|s| s.day == day && s.part == part)
+            .find_map(|s| {
+                let known = s.answer.parse::<i128>().ok();
+                match s.verdict {
+                    Verdict::Accepted => Some(format!(
+                        "day {} part {} was already accepted with {}",
+                        day, part, s.answer
+                    )),
+                    _ if s.answer == answer => Some(format!(
+                        "{} was already rejected for day {} part {} ({})",
+                        answer, day, part, s.verdict
+                    )),
+                    Verdict::TooHigh if value.zip(known).is_some_and(|(v, k)| v >= k) => {
+                        Some(format!(
+                            "{} cannot be right: {} was already too high",
+                            answer, s.answer
+                        ))
+                    }
+                    Verdict::TooLow if value.zip(known).is_some_and(|(v, k)| v <= k) => {
+                        Some(format!(
+                            "{} cannot be right: {} was already too low",
+                            answer, s.answer
+                        ))
+                    }
+                    _ => None,
+                }
+            })
+    }
+
+    /// Appends a submission to the log, on disk as well as in memory.
+    pub fn record(&mut self, submission: Submission) -> GenResult<()> {
+        if submission.answer.contains(['\t', '\n', '\r']) {
+            return Err("Answers containing tabs or line breaks cannot be recorded".into());
+        }
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let new = !self.path.exists();
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| Error::from(e).with_path(&self.path))?;
+
+        let mut out = String::new();
+        if new {
+            out.push_str(HEADER);
+            out.push('\n');
+        }
+        out.push_str(&format!(
+            "{}\t{}\t{}\t{}\t{}\n",
+            submission.day,
+            submission.part,
+            submission.answer,
+            submission.verdict,
+            submission.unix_time
+        ));
+        file.write_all(out.as_bytes())
+            .map_err(|e| Error::from(e).with_path(&self.path))?;
+
+        self.entries.push(submission);
+        Ok(())
+    }
+}
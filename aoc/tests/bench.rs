@@ -0,0 +1,46 @@
+use aoc::bench::{read_results, regressions, write_results, BenchResult};
+use std::time::Duration;
+
+fn result(day: u32, stage: &str, median_us: u64, allocs: u64) -> BenchResult {
+    BenchResult {
+        day,
+        stage: stage.to_string(),
+        iterations: 5,
+        min: Duration::from_micros(median_us / 2),
+        median: Duration::from_micros(median_us),
+        p95: Duration::from_micros(median_us * 2),
+        allocs,
+    }
+}
+
+#[test]
+fn results_file_round_trips() {
+    let path = std::env::temp_dir().join(format!("aoc-bench-{}.tsv", std::process::id()));
+    let results = vec![result(1, "parse", 10, 2), result(3, "part2", 16_000, 754)];
+
+    write_results(&path, &results).unwrap();
+    let read = read_results(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(read, results);
+}
+
+#[test]
+fn slower_or_allocating_stages_are_flagged() {
+    let baseline = vec![
+        result(3, "part1", 8_000, 1),
+        result(3, "part2", 16_000, 754),
+        result(8, "part2", 4_800, 100),
+    ];
+    let current = vec![
+        result(3, "part1", 8_500, 1),
+        result(3, "part2", 20_000, 754),
+        result(8, "part2", 4_800, 101),
+    ];
+
+    let found = regressions(&baseline, &current, 0.10);
+
+    assert_eq!(found.len(), 2, "{:?}", found);
+    assert!(found[0].starts_with("day 3 part2: median"));
+    assert_eq!(found[1], "day 8 part2: allocations 100 -> 101");
+}
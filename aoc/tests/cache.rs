@@ -0,0 +1,53 @@
+use aoc::cache::{Cache, CacheKey};
+use aoc::days::find_day;
+use aoc::run::solve_day;
+use std::fs;
+
+#[test]
+fn answers_are_reused_until_the_input_changes() {
+    let dir = std::env::temp_dir().join(format!("aoc-cache-test-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let cache_path = dir.join("cache.tsv");
+    let input_path = dir.join("input.txt");
+    let day = find_day(6).unwrap();
+
+    fs::write(
+        &input_path,
+        "Time:      7  15   30\nDistance:  9  40  200\n",
+    )
+    .unwrap();
+    let mut cache = Cache::load(&cache_path).unwrap();
+    let first = solve_day(day, &[1, 2], &input_path, Some(&mut cache)).unwrap();
+    cache.save().unwrap();
+    assert!(first.iter().all(|r| !r.cached));
+
+    let mut cache = Cache::load(&cache_path).unwrap();
+    let second = solve_day(day, &[1, 2], &input_path, Some(&mut cache)).unwrap();
+    assert!(second.iter().all(|r| r.cached));
+    assert_eq!(second[0].answer, "288");
+    assert_eq!(second[1].answer, "71503");
+
+    fs::write(&input_path, "Time:      7\nDistance:  9\n").unwrap();
+    let third = solve_day(day, &[1], &input_path, Some(&mut cache)).unwrap();
+    assert!(!third[0].cached);
+    assert_eq!(third[0].answer, "4");
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn a_new_solver_version_replaces_old_entries() {
+    let mut cache = Cache::load(std::env::temp_dir().join("aoc-cache-unsaved.tsv")).unwrap();
+    let key = |version: &str| CacheKey {
+        day: 1,
+        part: 1,
+        solver_version: version.to_string(),
+        input_sha256: "abc".to_string(),
+    };
+
+    cache.insert(key("v1"), "10");
+    cache.insert(key("v2"), "11");
+
+    assert_eq!(cache.get(&key("v1")), None);
+    assert_eq!(cache.get(&key("v2")), Some("11"));
+}
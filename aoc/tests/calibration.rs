@@ -0,0 +1,304 @@
+use aoc::days::workspace_root;
+use aoc_core::Error;
+use day1::{decimal_value, AhoCorasick, Calibration, Dictionary, DigitPolicy, Rules, Words};
+use std::fs;
+
+#[test]
+fn automaton_reports_overlapping_words() {
+    let automaton = AhoCorasick::new([("eight", 8), ("two", 2), ("three", 3), ("wo", 0)]);
+    let found: Vec<_> = automaton
+        .find_overlapping("eightwothree")
+        .map(|m| (m.span, m.value))
+        .collect();
+
+    assert_eq!(found, [(0..5, 8), (4..7, 2), (5..7, 0), (7..12, 3)]);
+}
+
+#[test]
+fn builtin_languages_can_be_combined() {
+    let mut dictionary = Dictionary::builtin("fr").unwrap();
+    dictionary
+        .extend(&Dictionary::builtin("de").unwrap())
+        .unwrap();
+    let calibration = Calibration::new(&dictionary, DigitPolicy::Ascii);
+
+    assert_eq!(calibration.sum("deux3zweiquatre\n").unwrap(), 24);
+    assert_eq!(calibration.sum("xxfünfxxachtx\n").unwrap(), 58);
+    // French "un" overlaps the end of German "neun" and comes last.
+    assert_eq!(calibration.sum("septneun\n").unwrap(), 71);
+}
+
+#[test]
+fn user_dictionaries_are_checked() {
+    let italian = Dictionary::parse("# Italian\nuno 1\n\ndue 2\n").unwrap();
+    let calibration = Calibration::new(&italian, DigitPolicy::Ascii);
+    assert_eq!(calibration.sum("unoxdue\n").unwrap(), 12);
+
+    let Error::Parse(e) = Dictionary::parse("uno 1\ndue 12\n").unwrap_err() else {
+        panic!("expected a parse error");
+    };
+    assert_eq!((e.line, e.span), (2, 4..6));
+
+    let mut english = Dictionary::builtin("en").unwrap();
+    let conflicting = Dictionary::parse("six 5\n").unwrap();
+    assert!(english.extend(&conflicting).is_err());
+    assert!(Dictionary::builtin("xx").is_err());
+}
+
+#[test]
+fn unicode_decimal_digits_map_to_their_value() {
+    assert_eq!(decimal_value('٣'), Some(3));
+    assert_eq!(decimal_value('९'), Some(9));
+    assert_eq!(decimal_value('０'), Some(0));
+    assert_eq!(decimal_value('𝟘'), Some(0));
+    assert_eq!(decimal_value('½'), None);
+    assert_eq!(decimal_value('²'), None);
+    assert_eq!(decimal_value('a'), None);
+
+    let digits_only = Dictionary::default();
+    let input = "a٣b½c7²\n";
+    let ascii = Calibration::new(&digits_only, DigitPolicy::Ascii);
+    assert_eq!(ascii.sum(input).unwrap(), 77);
+    let unicode = Calibration::new(&digits_only, DigitPolicy::Unicode);
+    assert_eq!(unicode.sum(input).unwrap(), 37);
+}
+
+#[test]
+fn reject_policy_points_at_the_numeral() {
+    let reject = Calibration::new(&Dictionary::default(), DigitPolicy::Reject);
+    assert_eq!(reject.sum("a1b2\n").unwrap(), 12);
+
+    let Error::Parse(e) = reject.sum("12\na٣b\n").unwrap_err() else {
+        panic!("expected a parse error");
+    };
+    assert_eq!((e.line, e.span), (2, 1..3));
+}
+
+#[test]
+fn explanations_show_every_match_and_the_chosen_pair() {
+    let calibration = Calibration::new(&Dictionary::builtin("en").unwrap(), DigitPolicy::Ascii);
+    let line = aoc_core::lines("xeightwo3twox").next().unwrap();
+    let explanation = calibration.explain(&line).unwrap();
+
+    assert_eq!(explanation.value, 82);
+    assert_eq!(
+        explanation.render(line.text, false),
+        "line 1: xeightwo3twox -> 82\n\
+         \x20 1..6   \"eight\"  8  first\n\
+         \x20 5..8   \"two\"    2\n\
+         \x20 8..9   \"3\"      3\n\
+         \x20 9..12  \"two\"    2  last\n"
+    );
+
+    let highlighted = explanation.render(line.text, true);
+    assert_eq!(
+        highlighted.lines().next().unwrap(),
+        "line 1: x\x1b[1;32meight\x1b[0m\x1b[36mwo3\x1b[0m\x1b[1;35mtwo\x1b[0mx -> 82"
+    );
+}
+
+#[test]
+fn scanning_from_both_ends_agrees_with_the_full_match_list() {
+    let mut everything = Dictionary::default();
+    for (language, _) in day1::LANGUAGES {
+        everything
+            .extend(&Dictionary::builtin(language).unwrap())
+            .unwrap();
+    }
+    // Words nested in longer ones, so the longest match must win at a
+    // given start and an earlier-starting word can end after a later one.
+    let nested = Dictionary::parse("abcde 1\nbc 2\nab 3\ncde 4\nd 5\n").unwrap();
+    let input = aoc_core::read_input(workspace_root().join("day1").join("input.txt")).unwrap();
+    let extra = "abcde\nxabcdex\nbcde9ab\n٣cdeab\nzabcd\n";
+
+    for dictionary in [Dictionary::default(), everything, nested] {
+        for digits in [DigitPolicy::Ascii, DigitPolicy::Unicode] {
+            let calibration = Calibration::new(&dictionary, digits);
+            for line in aoc_core::lines(&input).chain(aoc_core::lines(extra)) {
+                let expected = calibration.explain(&line).map(|e| e.value).ok();
+                assert_eq!(
+                    calibration.scan(&line).ok(),
+                    expected,
+                    "line {:?}",
+                    line.text
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn chunked_sums_match_regardless_of_where_chunks_split() {
+    let dir = std::env::temp_dir().join(format!("aoc-calibration-test-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("document.txt");
+    let text = "two1nine\r\neightwothree\n\nabcone2threexyz\nxtwone3four\n4nineeightseven2\nzoneight234\n7pqrstsixteen";
+    let calibration = Calibration::new(&Dictionary::builtin("en").unwrap(), DigitPolicy::Ascii);
+
+    // Without the blank line every chunking gives the puzzle's answer.
+    fs::write(&path, text.replace("\n\n", "\n")).unwrap();
+    for threads in 1..=text.len() + 1 {
+        assert_eq!(calibration.sum_file(&path, threads).unwrap(), 281);
+    }
+    let reader = std::io::Cursor::new(text.replace("\n\n", "\n"));
+    assert_eq!(calibration.sum_reader(reader).unwrap(), 281);
+
+    // The blank third line fails, and is reported as line 3 whichever
+    // chunk it falls in.
+    fs::write(&path, text).unwrap();
+    for threads in 1..=text.len() + 1 {
+        let Error::Parse(e) = calibration.sum_file(&path, threads).unwrap_err() else {
+            panic!("expected a parse error");
+        };
+        assert_eq!(e.line, 3, "with {} threads", threads);
+        assert_eq!(e.path.as_deref(), Some(path.as_path()));
+    }
+
+    fs::write(&path, b"12\n1\xff2\n").unwrap();
+    let Error::Parse(e) = calibration.sum_file(&path, 2).unwrap_err() else {
+        panic!("expected a parse error");
+    };
+    assert_eq!(
+        (e.line, e.span, e.expected.as_str()),
+        (2, 1..4, "valid UTF-8")
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn rule_sets_change_how_digits_are_taken() {
+    let english = Dictionary::builtin("en").unwrap();
+    let with =
+        |rules: &str| Calibration::with_rules(&english, DigitPolicy::Ascii, rules.parse().unwrap());
+
+    assert_eq!(with("aoc").sum("eightwo\n").unwrap(), 82);
+    assert_eq!(with("no-overlap").sum("eightwo\n").unwrap(), 88);
+    assert_eq!(with("first2-last2").sum("ab7cd12ef9\n").unwrap(), 7129);
+    // The first and last pairs may share digits.
+    assert_eq!(with("first2-last2").sum("x5y6\n").unwrap(), 5656);
+
+    let numbers = with("numbers");
+    assert_eq!(numbers.sum("twenty three\n").unwrap(), 23);
+    assert_eq!(numbers.sum("one hundred\n").unwrap(), 10);
+    assert_eq!(numbers.sum("x4 and twenty-one\n").unwrap(), 41);
+    assert_eq!(
+        numbers.sum("two million three hundred thousand\n").unwrap(),
+        20
+    );
+    // A unit cannot follow another, so "nine" starts a new number.
+    assert_eq!(numbers.sum("seven nine\n").unwrap(), 79);
+    // 9999 million does not fit in a u32, so "million" starts a new number.
+    assert_eq!(
+        numbers
+            .sum("ninety nine hundred ninety nine million\n")
+            .unwrap(),
+        90
+    );
+
+    let line = aoc_core::lines("four thousand-five hundred and 6")
+        .next()
+        .unwrap();
+    let explanation = numbers.explain(&line).unwrap();
+    let values: Vec<_> = explanation.matches.iter().map(|m| m.value).collect();
+    assert_eq!((values, explanation.value), (vec![4500, 6], 46));
+}
+
+#[test]
+fn rules_are_named_or_spelled_out() {
+    let rules: Rules = "first=3,last=1,words=numbers,overlap=yes".parse().unwrap();
+    assert_eq!(
+        (rules.first, rules.last, rules.words, rules.overlap),
+        (3, 1, Words::Numbers, true)
+    );
+    assert_eq!("aoc".parse::<Rules>().unwrap(), Rules::default());
+
+    for bad in [
+        "fancy",
+        "first=x",
+        "first=0,last=0",
+        "last=10",
+        "overlap=maybe",
+    ] {
+        assert!(bad.parse::<Rules>().is_err(), "{}", bad);
+    }
+
+    let needs_two = Calibration::with_rules(
+        &Dictionary::default(),
+        DigitPolicy::Ascii,
+        "first2-last2".parse().unwrap(),
+    );
+    let Error::Parse(e) = needs_two.sum("12\n3\n").unwrap_err() else {
+        panic!("expected a parse error");
+    };
+    assert_eq!(
+        (e.line, e.expected.as_str()),
+        (2, "at least 2 digits in the line")
+    );
+
+    // Each line is worth about 1e18, so twenty of them overflow the sum,
+    // which is an error even when lenient, however the file is split.
+    let eighteen = Calibration::with_rules(
+        &Dictionary::default(),
+        DigitPolicy::Ascii,
+        "first=9,last=9".parse().unwrap(),
+    );
+    let document = "999999999999999999\n".repeat(20);
+    assert!(matches!(eighteen.sum(&document), Err(Error::Msg(_))));
+    assert!(matches!(
+        eighteen.tally(&document, true),
+        Err(Error::Msg(_))
+    ));
+
+    let dir = std::env::temp_dir().join(format!("aoc-overflow-test-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("document.txt");
+    fs::write(&path, &document).unwrap();
+    for threads in [1, 4, 20] {
+        let result = eighteen.tally_file(&path, threads, true);
+        assert!(
+            matches!(result, Err(Error::Msg(_))),
+            "with {} threads",
+            threads
+        );
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn lenient_sums_skip_lines_without_a_value() {
+    let calibration = Calibration::new(&Dictionary::builtin("en").unwrap(), DigitPolicy::Ascii);
+    let text = "two1nine\nnothing\n7x\n\nzoneight234\n";
+    assert!(calibration.sum(text).is_err());
+
+    let tally = calibration.tally(text, true).unwrap();
+    assert_eq!((tally.sum, tally.lines), (29 + 77 + 14, 5));
+    let skipped: Vec<_> = tally.skipped.iter().map(|e| e.line).collect();
+    assert_eq!(skipped, [2, 4]);
+    assert_eq!(
+        tally.report(),
+        "skipped 2 of 5 lines\n\
+         \x20 line 2: expected a digit or spelled-out digit in the line\n\
+         \x20 line 4: expected a digit or spelled-out digit in the line\n"
+    );
+
+    let dir = std::env::temp_dir().join(format!("aoc-lenient-test-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("document.txt");
+    fs::write(&path, b"12\n1\xff2\nabc\n34\n").unwrap();
+    for threads in 1..=8 {
+        let tally = calibration.tally_file(&path, threads, true).unwrap();
+        let skipped: Vec<_> = tally.skipped.iter().map(|e| e.line).collect();
+        assert_eq!(
+            (tally.sum, skipped),
+            (46, vec![2, 3]),
+            "with {} threads",
+            threads
+        );
+        assert!(tally
+            .skipped
+            .iter()
+            .all(|e| e.path.as_deref() == Some(path.as_path())));
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
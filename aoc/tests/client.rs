@@ -0,0 +1,133 @@
+use aoc::client::{Client, Config};
+use aoc::submissions::{Submissions, Verdict};
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// A stand-in for the puzzle server: serves a fixed input and accepts only
+/// `42` for part 1, recording every request line and body it sees.
+fn stand_in_server() -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let seen = Arc::new(Mutex::new(vec![]));
+    let log = Arc::clone(&seen);
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(&stream);
+
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut content_length = 0;
+            let mut cookie = String::new();
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                if header.trim().is_empty() {
+                    break;
+                }
+                let (name, value) = header.split_once(':').unwrap();
+                match name.to_ascii_lowercase().as_str() {
+                    "content-length" => content_length = value.trim().parse().unwrap(),
+                    "cookie" => cookie = value.trim().to_string(),
+                    _ => {}
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            let body = String::from_utf8(body).unwrap();
+
+            let request = format!("{} {}", request_line.trim(), body);
+            let (status, page) = if cookie != "session=secret" {
+                (400, "Puzzle inputs differ by user.")
+            } else if request.starts_with("GET /2023/day/3/input ") {
+                (200, "467..114..\n...*......\n")
+            } else if body == "level=1&answer=42" {
+                (200, "<article><p>That's the right answer!</p></article>")
+            } else if body == "level=1&answer=100" {
+                (200, "<article><p>That's not the right answer; your answer is too high.</p></article>")
+            } else {
+                (
+                    200,
+                    "<article><p>That's not the right answer.</p></article>",
+                )
+            };
+            log.lock().unwrap().push(request);
+
+            write!(
+                stream,
+                "HTTP/1.1 {} X\r\nContent-Length: {}\r\n\r\n{}",
+                status,
+                page.len(),
+                page
+            )
+            .unwrap();
+        }
+    });
+
+    (base_url, seen)
+}
+
+fn client(base_url: &str, session: &str) -> Client {
+    Client::new(Config {
+        session: session.to_string(),
+        base_url: base_url.to_string(),
+        year: 2023,
+    })
+}
+
+#[test]
+fn fetches_inputs_with_the_session_cookie() {
+    let (base_url, _) = stand_in_server();
+
+    let input = client(&base_url, "secret").fetch_input(3).unwrap();
+    assert_eq!(input, "467..114..\n...*......\n");
+
+    let err = client(&base_url, "wrong").fetch_input(3).unwrap_err();
+    assert!(err.to_string().contains("returned 400"), "{}", err);
+}
+
+#[test]
+fn submissions_are_recorded_and_known_wrong_answers_are_not_resent() {
+    let (base_url, seen) = stand_in_server();
+    let dir = std::env::temp_dir().join(format!("aoc-client-test-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let log_path = dir.join("submissions.tsv");
+    let client = client(&base_url, "secret");
+
+    let mut log = Submissions::load(&log_path).unwrap();
+    assert_eq!(client.submit(&mut log, 3, 1, "7").unwrap(), Verdict::Wrong);
+    assert_eq!(
+        client.submit(&mut log, 3, 1, "100").unwrap(),
+        Verdict::TooHigh
+    );
+
+    // Reloading from disk keeps the verdicts, so neither the same answer
+    // nor one ruled out by the "too high" hint reaches the server.
+    let mut log = Submissions::load(&log_path).unwrap();
+    assert_eq!(log.entries().len(), 2);
+    assert!(client.submit(&mut log, 3, 1, "7").is_err());
+    assert!(client.submit(&mut log, 3, 1, "150").is_err());
+    // Nor does one the log could not record afterwards.
+    assert!(client.submit(&mut log, 3, 1, "4\t2").is_err());
+    assert_eq!(seen.lock().unwrap().len(), 2);
+
+    assert_eq!(
+        client.submit(&mut log, 3, 1, "42").unwrap(),
+        Verdict::Accepted
+    );
+    assert!(client.submit(&mut log, 3, 1, "43").is_err());
+    assert_eq!(
+        *seen.lock().unwrap(),
+        [
+            "POST /2023/day/3/answer HTTP/1.1 level=1&answer=7",
+            "POST /2023/day/3/answer HTTP/1.1 level=1&answer=100",
+            "POST /2023/day/3/answer HTTP/1.1 level=1&answer=42",
+        ]
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
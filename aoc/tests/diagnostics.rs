@@ -0,0 +1,38 @@
+use aoc::days::find_day;
+use std::path::Path;
+
+fn parse_error(day: u32, input: &str) -> String {
+    let day = find_day(day).unwrap();
+    match day.solution.parse(input) {
+        Ok(_) => panic!("expected day {} to reject {:?}", day.number, input),
+        Err(e) => e.with_path(Path::new("fixture.txt")).to_string(),
+    }
+}
+
+#[test]
+fn missing_delimiter_points_past_the_end_of_the_line() {
+    let input = "Card 1: 41 48 | 83 86\nCard 2: 13 32 20 16 61\n";
+
+    assert_eq!(
+        parse_error(4, input),
+        "expected '|'\n \
+         --> fixture.txt:2:23\n  \
+         |\n\
+         2 | Card 2: 13 32 20 16 61\n  \
+         |                       ^"
+    );
+}
+
+#[test]
+fn bad_token_is_underlined() {
+    let input = "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 5O 48\n";
+
+    assert_eq!(
+        parse_error(5, input),
+        "expected a number\n \
+         --> fixture.txt:5:4\n  \
+         |\n\
+         5 | 52 5O 48\n  \
+         |    ^^"
+    );
+}
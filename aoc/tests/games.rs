@@ -0,0 +1,353 @@
+use aoc_core::{Error, Solution};
+use day2::{
+    check_ids, describe, elf_bag, first_offense, game_power, is_valid_game, log_likelihood,
+    min_bag, most_likely_bag, parse_bag, parse_games, query, smallest_bag, sort_summaries,
+    summarize, Column, Cubes, Day2, IdIssue, Offense, Palette, ParseOptions, Summary,
+};
+
+const SAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+";
+
+#[test]
+fn ids_come_from_the_header() {
+    // Games 3 and 4 are impossible; keep 5, 2 and 1, in that order.
+    let filtered: String = SAMPLE
+        .lines()
+        .rev()
+        .filter(|l| !l.contains("20 red") && !l.contains("15 blue"))
+        .map(|l| format!("{}\n", l))
+        .collect();
+    let games = Day2::parse(&filtered).unwrap();
+    assert_eq!(Day2::part1(&games).unwrap(), 8);
+
+    assert_eq!(
+        check_ids(&games),
+        [
+            IdIssue::OutOfOrder {
+                id: 2,
+                line: 2,
+                after: 5
+            },
+            IdIssue::OutOfOrder {
+                id: 1,
+                line: 3,
+                after: 2
+            },
+            IdIssue::Missing { from: 3, to: 4 },
+        ]
+    );
+    assert!(check_ids(&Day2::parse(SAMPLE).unwrap()).is_empty());
+    assert_eq!(
+        IdIssue::Missing { from: 3, to: 4 }.to_string(),
+        "games 3 to 4 are missing"
+    );
+
+    // The largest ID is valid, but cannot be added to another.
+    let huge = Day2::parse("Game 18446744073709551615: 1 red\nGame 2: 1 red\n").unwrap();
+    assert!(Day2::part1(&huge).is_err());
+    assert_eq!(
+        check_ids(&huge),
+        [
+            IdIssue::OutOfOrder {
+                id: 2,
+                line: 2,
+                after: usize::MAX
+            },
+            IdIssue::Missing { from: 1, to: 1 },
+            IdIssue::Missing {
+                from: 3,
+                to: usize::MAX - 1
+            },
+        ]
+    );
+}
+
+#[test]
+fn bad_or_repeated_ids_are_errors() {
+    let cases = [
+        (
+            "Game 1: 1 red\nGame 1: 2 red\n",
+            2,
+            5..6,
+            "a game ID not already used on line 1",
+        ),
+        ("Round 1: 1 red\n", 1, 0..7, "'Game <id>'"),
+        ("Game x: 1 red\n", 1, 5..6, "a game ID"),
+        ("Game 0: 1 red\n", 1, 5..6, "a game ID of at least 1"),
+    ];
+    for (input, line, span, expected) in cases {
+        let Error::Parse(e) = parse_games(input, &ParseOptions::default()).unwrap_err() else {
+            panic!("expected a parse error for {:?}", input);
+        };
+        assert_eq!(
+            (e.line, e.span, e.expected.as_str()),
+            (line, span, expected)
+        );
+    }
+}
+
+fn cubes(counts: &[(&str, i32)]) -> Cubes {
+    counts.iter().map(|&(c, n)| (c.to_string(), n)).collect()
+}
+
+#[test]
+fn palettes_choose_the_colors_a_log_may_use() {
+    let log = "Game 1: 2 red, 1 violet; 3 teal\nGame 2: 4 violet, 1 red\n";
+
+    let Error::Parse(e) = parse_games(log, &ParseOptions::default()).unwrap_err() else {
+        panic!("expected a parse error");
+    };
+    assert_eq!(
+        (e.span, e.expected.as_str()),
+        (17..23, "red, green or blue")
+    );
+
+    let known = ParseOptions {
+        palette: "red, violet".parse().unwrap(),
+        merge: false,
+    };
+    let Error::Parse(e) = parse_games(log, &known).unwrap_err() else {
+        panic!("expected a parse error");
+    };
+    assert_eq!(e.expected, "red or violet");
+    assert!("red,,blue".parse::<Palette>().is_err());
+    assert!("red,red".parse::<Palette>().is_err());
+
+    let open = ParseOptions {
+        palette: Palette::Open,
+        merge: false,
+    };
+    let games = parse_games(log, &open).unwrap();
+    let colors = open.palette.colors(&games);
+    assert_eq!(colors, ["red", "teal", "violet"]);
+    assert_eq!(
+        min_bag(&games[0].rounds),
+        cubes(&[("red", 2), ("teal", 3), ("violet", 1)])
+    );
+    assert_eq!(game_power(&games[0].rounds, &colors).unwrap(), 6);
+    // Game 2 draws no teal, so could have been played without any.
+    assert_eq!(game_power(&games[1].rounds, &colors).unwrap(), 0);
+
+    let bag = cubes(&[("red", 2), ("violet", 4)]);
+    assert!(!is_valid_game(&games[0].rounds, &bag));
+    assert!(is_valid_game(&games[1].rounds, &bag));
+
+    // 2000000000³ does not fit in an i64.
+    let huge = Day2::parse("Game 1: 2000000000 red, 2000000000 green, 2000000000 blue\n").unwrap();
+    let err = Day2::part2(&huge).unwrap_err();
+    assert!(err.to_string().contains("Game 1"), "{}", err);
+}
+
+#[test]
+fn bags_are_written_like_rounds() {
+    assert_eq!(parse_bag("12 red, 13 green, 14 blue").unwrap(), elf_bag());
+    assert_eq!(
+        parse_bag("# the elf's bag\n12 red\n13 green, 14 blue # and no more\n\n").unwrap(),
+        elf_bag()
+    );
+
+    let cases = [
+        ("12 red, 3 red", 1, 10..13, "a color not already in the bag"),
+        ("12 red\n-1 blue", 2, 0..2, "a cube count of at least 0"),
+        ("red", 1, 3..4, "' '"),
+    ];
+    for (text, line, span, expected) in cases {
+        let Error::Parse(e) = parse_bag(text).unwrap_err() else {
+            panic!("expected a parse error for {:?}", text);
+        };
+        assert_eq!(
+            (e.line, e.span, e.expected.as_str()),
+            (line, span, expected)
+        );
+    }
+
+    let colors = Palette::default().colors(&[]);
+    assert_eq!(
+        describe(&cubes(&[("blue", 2), ("pink", 1)]), &colors),
+        "0 red, 0 green, 2 blue, 1 pink"
+    );
+}
+
+#[test]
+fn queries_name_the_first_round_a_bag_cannot_supply() {
+    let games = Day2::parse(SAMPLE).unwrap();
+    let (feasible, infeasible) = query(&games, &elf_bag());
+
+    let ids: Vec<_> = feasible.iter().map(|g| g.id).collect();
+    assert_eq!(ids, [1, 2, 5]);
+    let offenses: Vec<_> = infeasible.iter().map(|(g, o)| (g.id, o.clone())).collect();
+    assert_eq!(
+        offenses,
+        [
+            (
+                3,
+                Offense {
+                    round: 0,
+                    over: vec![("red".to_string(), 20, 12)]
+                }
+            ),
+            (
+                4,
+                Offense {
+                    round: 2,
+                    over: vec![("blue".to_string(), 15, 14), ("red".to_string(), 14, 12)]
+                }
+            ),
+        ]
+    );
+
+    // The smallest bag for a set of games allows exactly those of them.
+    let bag = smallest_bag(games.iter().filter(|g| g.id != 3));
+    assert_eq!(bag, cubes(&[("red", 14), ("green", 3), ("blue", 15)]));
+    assert!(first_offense(&games[3].rounds, &bag).is_none());
+    assert_eq!(first_offense(&games[2].rounds, &bag).unwrap().round, 0);
+    assert_eq!(smallest_bag([]), Cubes::new());
+}
+
+#[test]
+fn malformed_rounds_name_the_game_and_round() {
+    let cases = [
+        (
+            "Game 3: 1 red; 3 red, 4 red\n",
+            24..27,
+            "a color not already drawn in game 3, round 2",
+        ),
+        (
+            "Game 3: 0 red\n",
+            8..9,
+            "a cube count of at least 1 in game 3, round 1",
+        ),
+        (
+            "Game 3: 1 red; -2 blue\n",
+            15..17,
+            "a cube count of at least 1 in game 3, round 2",
+        ),
+        (
+            "Game 3: 1 red;; 2 blue\n",
+            14..15,
+            "at least one draw in game 3, round 2",
+        ),
+        (
+            "Game 3: 1 red; 2 blue;\n",
+            22..23,
+            "at least one draw in game 3, round 3",
+        ),
+        (
+            "Game 3: 1 red, 2\n",
+            15..16,
+            "'<count> <color>' in game 3, round 1",
+        ),
+    ];
+    for (input, span, expected) in cases {
+        let Error::Parse(e) = parse_games(input, &ParseOptions::default()).unwrap_err() else {
+            panic!("expected a parse error for {:?}", input);
+        };
+        assert_eq!(
+            (e.span, e.expected.as_str()),
+            (span, expected),
+            "{:?}",
+            input
+        );
+    }
+
+    let merge = ParseOptions {
+        merge: true,
+        ..ParseOptions::default()
+    };
+    let games = parse_games("Game 1: 3 red, 2 blue, 4 red; 1 red\n", &merge).unwrap();
+    assert_eq!(games[0].rounds[0], cubes(&[("red", 7), ("blue", 2)]));
+    assert!(parse_games("Game 1: 0 red, 4 red\n", &merge).is_err());
+    let Error::Parse(e) =
+        parse_games("Game 1: 2000000000 red, 2000000000 red\n", &merge).unwrap_err()
+    else {
+        panic!("expected a parse error");
+    };
+    assert_eq!(
+        (e.span, e.expected.as_str()),
+        (
+            35..38,
+            "red counts adding up to at most 2147483647 in game 1, round 1"
+        )
+    );
+}
+
+#[test]
+fn likelihoods_treat_rounds_as_draws_without_replacement() {
+    let bag = cubes(&[("red", 2), ("blue", 3)]);
+    // 1 red and 1 blue: 2 * 3 of the C(5, 2) = 10 pairs.
+    let round = cubes(&[("red", 1), ("blue", 1)]);
+    assert!((log_likelihood(std::slice::from_ref(&round), &bag).exp() - 0.6).abs() < 1e-12);
+    assert!((log_likelihood(&[round.clone(), round], &bag).exp() - 0.36).abs() < 1e-12);
+    assert_eq!(
+        log_likelihood(&[cubes(&[("red", 3)])], &bag),
+        f64::NEG_INFINITY
+    );
+
+    // Drawing the same cubes every time is certain from exactly those.
+    let colors = vec!["red".to_string(), "blue".to_string()];
+    let same =
+        Day2::parse("Game 1: 2 red, 1 blue; 1 blue, 2 red\nGame 2: 2 red, 1 blue\n").unwrap();
+    let estimate = most_likely_bag(&same, &colors, 50).unwrap();
+    assert_eq!(estimate.bag, cubes(&[("red", 2), ("blue", 1)]));
+    assert_eq!((estimate.log_likelihood, estimate.at_limit), (0.0, false));
+    assert!(most_likely_bag(&same, &colors, 2).is_err());
+}
+
+#[test]
+fn the_most_likely_bag_matches_an_exhaustive_search() {
+    // The smallest bag is 4 red and 2 blue, but one more red is likelier.
+    let log = "Game 1: 4 red, 2 blue; 1 red; 2 red, 2 blue; 3 red\n\
+               Game 2: 1 red, 1 blue; 4 red, 1 blue; 2 red; 1 blue\n";
+    let games = Day2::parse(log).unwrap();
+    let colors = vec!["red".to_string(), "blue".to_string()];
+    let total = |bag: &Cubes| -> f64 { games.iter().map(|g| log_likelihood(&g.rounds, bag)).sum() };
+
+    let max_cubes = 40;
+    let mut best = (f64::NEG_INFINITY, Cubes::new());
+    for red in 0..=max_cubes {
+        for blue in 0..=max_cubes - red {
+            let bag = cubes(&[("red", red), ("blue", blue)]);
+            let ln = total(&bag);
+            if ln > best.0 + 1e-9 {
+                best = (ln, bag);
+            }
+        }
+    }
+
+    let estimate = most_likely_bag(&games, &colors, max_cubes as usize).unwrap();
+    assert_eq!(estimate.bag, cubes(&[("red", 5), ("blue", 2)]));
+    assert_eq!((&estimate.bag, estimate.at_limit), (&best.1, false));
+    assert!((estimate.log_likelihood - best.0).abs() < 1e-9);
+}
+
+#[test]
+fn reports_summarize_each_game_once_and_sort_by_any_column() {
+    let games = Day2::parse(SAMPLE).unwrap();
+    let colors = Palette::default().colors(&games);
+    let mut rows = summarize(&games, &elf_bag(), &colors).unwrap();
+
+    let row = &rows[3];
+    assert_eq!(
+        (row.id, row.rounds, row.power, row.feasible),
+        (4, 3, 630, false)
+    );
+    assert_eq!(row.most, cubes(&[("red", 14), ("green", 3), ("blue", 15)]));
+    let powers: i64 = rows.iter().map(|r| r.power).sum();
+    assert_eq!(powers, Day2::part2(&games).unwrap());
+
+    let order = |rows: &[Summary]| rows.iter().map(|r| r.id).collect::<Vec<_>>();
+    sort_summaries(&mut rows, &"power".parse().unwrap(), &colors, true).unwrap();
+    assert_eq!(order(&rows), [3, 4, 1, 5, 2]);
+    sort_summaries(&mut rows, &"blue".parse().unwrap(), &colors, false).unwrap();
+    assert_eq!(order(&rows), [5, 2, 3, 1, 4]);
+    // Ties keep the previous order.
+    sort_summaries(&mut rows, &Column::Rounds, &colors, false).unwrap();
+    assert_eq!(order(&rows), [5, 2, 3, 1, 4]);
+    sort_summaries(&mut rows, &Column::Feasible, &colors, false).unwrap();
+    assert_eq!(order(&rows), [3, 4, 5, 2, 1]);
+    assert!(sort_summaries(&mut rows, &"teal".parse().unwrap(), &colors, false).is_err());
+}
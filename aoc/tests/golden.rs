@@ -0,0 +1,27 @@
+use aoc::days::DAYS;
+use aoc::golden::{check_day, fixtures, load_manifest};
+
+#[test]
+fn every_fixture_matches_its_golden_answer() {
+    let failures: Vec<String> = DAYS
+        .iter()
+        .flat_map(|day| check_day(day).unwrap())
+        .collect();
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+#[test]
+fn every_fixture_has_a_golden_answer() {
+    for day in DAYS {
+        let manifest = load_manifest(day).unwrap();
+        for fixture in fixtures(day).unwrap() {
+            assert!(
+                manifest.iter().any(|e| e.fixture == fixture),
+                "day {} fixture {} has no entry in answers.txt",
+                day.number,
+                fixture
+            );
+        }
+    }
+}
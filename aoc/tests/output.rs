@@ -0,0 +1,49 @@
+use aoc::output::{render, Format, Record};
+use aoc::sha256::sha256_hex;
+use std::time::Duration;
+
+fn record(answer: &str) -> Record {
+    Record {
+        day: 2,
+        part: 1,
+        answer: answer.to_string(),
+        parse_time: Duration::from_nanos(1500),
+        solve_time: Duration::from_nanos(250),
+        cached: false,
+        input: "day2/test.txt".to_string(),
+        input_sha256: "ab12".to_string(),
+    }
+}
+
+#[test]
+fn sha256_matches_reference_vectors() {
+    assert_eq!(
+        sha256_hex(b""),
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+    assert_eq!(
+        sha256_hex(b"abc"),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    assert_eq!(
+        sha256_hex(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+        "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+    );
+}
+
+#[test]
+fn jsonl_escapes_strings() {
+    assert_eq!(
+        render(Format::JsonLines, &record("say \"hi\"")),
+        "{\"day\":2,\"part\":1,\"answer\":\"say \\\"hi\\\"\",\"parse_ns\":1500,\
+         \"solve_ns\":250,\"cached\":false,\"input\":\"day2/test.txt\",\"input_sha256\":\"ab12\"}"
+    );
+}
+
+#[test]
+fn csv_quotes_fields_with_commas() {
+    assert_eq!(
+        render(Format::Csv, &record("1,2")),
+        "2,1,\"1,2\",1500,250,false,day2/test.txt,ab12"
+    );
+}
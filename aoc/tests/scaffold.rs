@@ -0,0 +1,48 @@
+use aoc::days::workspace_root;
+use aoc::scaffold::new_day;
+use std::fs;
+
+#[test]
+fn new_day_creates_the_crate_and_registers_it_in_order() {
+    let root = std::env::temp_dir().join(format!("aoc-scaffold-test-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("aoc").join("src")).unwrap();
+    for file in ["Cargo.toml", "src/days.rs"] {
+        fs::copy(
+            workspace_root().join("aoc").join(file),
+            root.join("aoc").join(file),
+        )
+        .unwrap();
+    }
+
+    new_day(&root, 10).unwrap();
+    for file in [
+        "Cargo.toml",
+        "day10.rs",
+        "input.txt",
+        "test.txt",
+        "answers.txt",
+    ] {
+        assert!(root.join("day10").join(file).exists(), "missing {}", file);
+    }
+    assert!(fs::read_to_string(root.join("day10/answers.txt"))
+        .unwrap()
+        .contains("input.txt 1 ?"));
+    // An unsolved day must not stop `aoc all` or `aoc bench`.
+    let stub = fs::read_to_string(root.join("day10/day10.rs")).unwrap();
+    assert!(stub.contains("Ok(0)") && !stub.contains("Err("));
+
+    let manifest = fs::read_to_string(root.join("aoc/Cargo.toml")).unwrap();
+    assert!(manifest.contains("day9 = { path = \"../day9\" }\nday10 = { path = \"../day10\" }\n"));
+    let days = fs::read_to_string(root.join("aoc/src/days.rs")).unwrap();
+    let day9 = days.find("solution: &day9::Day9").unwrap();
+    let day10 = days.find("solution: &day10::Day10").unwrap();
+    assert!(day9 < day10);
+
+    // Already registered days are refused before anything is written.
+    assert!(new_day(&root, 3).is_err());
+    assert!(!root.join("day3").exists());
+    assert!(new_day(&root, 10).is_err());
+
+    fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,86 @@
+use aoc::days::workspace_root;
+use aoc_core::{Pos, Solution};
+use day3::{total_value, Aggregate, Count, Day3, GearRules};
+
+#[test]
+fn large_schematics_sum_like_their_parts() {
+    let sample = aoc_core::read_input(workspace_root().join("day3").join("test.txt")).unwrap();
+    let width = sample.lines().next().unwrap().len();
+    let blank = ".".repeat(width);
+
+    // Copies separated by blank rows cannot touch, so each adds the same.
+    let copies = 500;
+    let block = format!("{}\n{}\n", sample.trim_end(), blank);
+    let schematic = Day3::parse(&block.repeat(copies)).unwrap();
+
+    assert_eq!(Day3::part1(&schematic).unwrap(), 4361 * copies as i64);
+    assert_eq!(Day3::part2(&schematic).unwrap(), 467835 * copies as i64);
+}
+
+#[test]
+fn gear_rules_choose_symbols_counts_and_aggregates() {
+    let sample = aoc_core::read_input(workspace_root().join("day3").join("test.txt")).unwrap();
+    let schematic = Day3::parse(&sample).unwrap();
+    let total = |rules: &GearRules| total_value(&schematic.gears(rules).unwrap()).unwrap();
+
+    let puzzle = GearRules::default();
+    let gears = schematic.gears(&puzzle).unwrap();
+    assert_eq!(gears[0].pos, Pos::new(1, 3));
+    assert_eq!(
+        (gears[0].symbol, gears[0].numbers.clone()),
+        ('*', vec![467, 35])
+    );
+    assert_eq!(total(&puzzle), 467835);
+
+    let stars_touching_one = GearRules {
+        count: "1".parse().unwrap(),
+        ..GearRules::default()
+    };
+    assert_eq!(total(&stars_touching_one), 617);
+
+    let any_largest = GearRules {
+        symbols: None,
+        count: ">=1".parse().unwrap(),
+        aggregate: "max".parse().unwrap(),
+    };
+    assert_eq!(total(&any_largest), 467 + 633 + 617 + 592 + 664 + 755);
+
+    let sums_of_pairs_or_fewer = GearRules {
+        symbols: Some(vec!['*', '#']),
+        count: Count::AtMost(2),
+        aggregate: Aggregate::Sum,
+    };
+    assert_eq!(total(&sums_of_pairs_or_fewer), 502 + 633 + 617 + 1353);
+
+    assert_eq!("<=3".parse::<Count>().unwrap(), Count::AtMost(3));
+    assert!("~2".parse::<Count>().is_err());
+    assert!("mean".parse::<Aggregate>().is_err());
+}
+
+#[test]
+fn ragged_schematics_are_padded_with_empty_cells() {
+    let schematic = Day3::parse("467..114\n...*\n..35..633.\n").unwrap();
+    assert_eq!(Day3::part1(&schematic).unwrap(), 467 + 35);
+    assert_eq!(Day3::part2(&schematic).unwrap(), 467 * 35);
+}
+
+#[test]
+fn gear_values_too_large_for_an_i64_are_errors() {
+    let schematic =
+        Day3::parse("999999999.999999999\n999999999*999999999\n999999999.999999999\n").unwrap();
+    let at_least_two = GearRules {
+        count: ">=2".parse().unwrap(),
+        ..GearRules::default()
+    };
+    let err = schematic.gears(&at_least_two).unwrap_err();
+    assert!(err.to_string().contains("2:10"), "{}", err);
+
+    // Part numbers too large to add up in an i32 are fine in an i64.
+    let schematic = Day3::parse("999999999*999999999*999999999\n").unwrap();
+    assert_eq!(Day3::part1(&schematic).unwrap(), 2999999997);
+
+    // Each pair's product fits, but not their sum.
+    let pairs = format!("999999999*999999999\n{}\n", ".".repeat(19)).repeat(10);
+    let schematic = Day3::parse(&pairs).unwrap();
+    assert!(Day3::part2(&schematic).is_err());
+}
@@ -0,0 +1,115 @@
+//! An Aho-Corasick automaton for finding every occurrence of a set of words
+//! in one pass, overlaps included ("eightwo" holds both "eight" and "two").
+
+use std::collections::VecDeque;
+use std::ops::Range;
+
+const NONE: u32 = u32::MAX;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Match {
+    /// Byte span in the searched text.
+    pub span: Range<usize>,
+    pub value: u32,
+}
+
+pub struct AhoCorasick {
+    /// Fully resolved transitions, one row of 256 per state, so searching
+    /// never follows failure links.
+    delta: Vec<[u32; 256]>,
+    /// The patterns ending at each state, its own and those of its suffixes.
+    outputs: Vec<Vec<usize>>,
+    patterns: Vec<(usize, u32)>,
+    max_len: usize,
+}
+
+impl AhoCorasick {
+    /// Builds the automaton for `(word, value)` pairs. Empty words are
+    /// ignored. Words are byte strings so that reversed words can be used
+    /// to search backwards.
+    pub fn new<W: AsRef<[u8]>>(words: impl IntoIterator<Item = (W, u32)>) -> Self {
+        let mut delta = vec![[NONE; 256]];
+        let mut outputs = vec![vec![]];
+        let mut patterns = vec![];
+
+        for (word, value) in words {
+            let word = word.as_ref();
+            if word.is_empty() {
+                continue;
+            }
+
+            let mut state = 0;
+            for &b in word {
+                if delta[state][b as usize] == NONE {
+                    delta[state][b as usize] = delta.len() as u32;
+                    delta.push([NONE; 256]);
+                    outputs.push(vec![]);
+                }
+                state = delta[state][b as usize] as usize;
+            }
+            outputs[state].push(patterns.len());
+            patterns.push((word.len(), value));
+        }
+
+        // Breadth-first, so a state's failure target is always resolved
+        // before the state itself.
+        let mut fail = vec![0; delta.len()];
+        let mut queue = VecDeque::new();
+        for child in delta[0].iter_mut() {
+            match *child {
+                NONE => *child = 0,
+                child => queue.push_back(child as usize),
+            }
+        }
+        while let Some(state) = queue.pop_front() {
+            let fallback = delta[fail[state]];
+            for (b, &target) in fallback.iter().enumerate() {
+                match delta[state][b] {
+                    NONE => delta[state][b] = target,
+                    child => {
+                        let child = child as usize;
+                        fail[child] = target as usize;
+                        let inherited = outputs[target as usize].clone();
+                        outputs[child].extend(inherited);
+                        queue.push_back(child);
+                    }
+                }
+            }
+        }
+
+        let max_len = patterns.iter().map(|&(len, _)| len).max().unwrap_or(0);
+        AhoCorasick {
+            delta,
+            outputs,
+            patterns,
+            max_len,
+        }
+    }
+
+    /// The length in bytes of the longest word.
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    /// The state after reading `byte` in `state`; searches start in state 0.
+    pub fn step(&self, state: usize, byte: u8) -> usize {
+        self.delta[state][byte as usize] as usize
+    }
+
+    /// The `(length, value)` of each word ending at `state`.
+    pub fn words_at(&self, state: usize) -> impl Iterator<Item = (usize, u32)> + '_ {
+        self.outputs[state].iter().map(|&id| self.patterns[id])
+    }
+
+    /// Every occurrence of every word in `text`, ordered by where it ends.
+    pub fn find_overlapping<'a>(&'a self, text: &'a str) -> impl Iterator<Item = Match> + 'a {
+        let mut state = 0;
+        text.bytes().enumerate().flat_map(move |(i, b)| {
+            state = self.step(state, b);
+            self.words_at(state).map(move |(len, value)| Match {
+                span: i + 1 - len..i + 1,
+                value,
+            })
+        })
+    }
+}
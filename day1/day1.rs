@@ -1,91 +1,149 @@
-use std::error;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
-
-fn main() -> Result<(), Box<dyn error::Error>> {
-    let file = File::open("input.txt")?;
-    let reader = BufReader::new(file);
-
-    let mut sum1 = 0;
-    let mut sum2 = 0;
-    for line_res in reader.lines() {
-        let line = line_res?;
-        let number1 = parse_number_p1(&line)?;
-        sum1 += number1;
-
-        let number2 = parse_number_p2(&line)?;
-        sum2 += number2;
+mod automaton;
+mod dictionary;
+mod digits;
+mod explain;
+mod rules;
+mod stream;
+mod tally;
+
+pub use automaton::{AhoCorasick, Match};
+pub use dictionary::{Dictionary, LANGUAGES};
+pub use digits::{decimal_value, CharClass, DigitPolicy};
+pub use explain::Explanation;
+pub use rules::{Rules, Words, NUMBER_WORDS, RULE_SETS};
+pub use tally::Tally;
+
+use aoc_core::{lines, GenResult, Line, Solution};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Parsed = String;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> GenResult<String> {
+        Ok(input.to_string())
     }
 
-    println!("part 1: {}", sum1);
-    println!("part 2: {}", sum2);
+    fn part1(input: &String) -> GenResult<u64> {
+        Calibration::new(&Dictionary::default(), DigitPolicy::Ascii).sum(input)
+    }
 
-    Ok(())
+    fn part2(input: &String) -> GenResult<u64> {
+        Calibration::new(&Dictionary::builtin("en")?, DigitPolicy::Ascii).sum(input)
+    }
 }
 
-fn parse_number_p1(line: &str) -> Result<i32, Box<dyn error::Error>> {
-    let digit_one = line
-        .chars()
-        .find(|&c| char::is_numeric(c))
-        .ok_or(format!("Could not find first digit: {}", line))?;
-
-    let digit_two = line
-        .chars()
-        .rfind(|&c| char::is_numeric(c))
-        .ok_or(format!("Could not find second digit: {}", line))?;
-
-    let res = format!("{}{}", digit_one, digit_two).parse::<i32>()?;
-
-    Ok(res)
+/// How calibration values are read from a document: the spelled-out
+/// digits to recognise, which characters count as digits and the rules
+/// combining them.
+pub struct Calibration {
+    pub digits: DigitPolicy,
+    rules: Rules,
+    words: AhoCorasick,
+    reversed: AhoCorasick,
+    spelled_out: bool,
 }
 
-fn parse_number_p2(line: &str) -> Result<i32, Box<dyn error::Error>> {
-    fn consume_number(s: &str) -> Option<i32> {
-        let head = s.chars().next()?;
+impl Calibration {
+    /// Part 1 is an empty dictionary, part 2 the English one, both with the
+    /// default rules.
+    pub fn new(dictionary: &Dictionary, digits: DigitPolicy) -> Self {
+        Calibration::with_rules(dictionary, digits, Rules::default())
+    }
 
-        if char::is_numeric(head) {
-            head.to_digit(10).map(|n| n as i32)
-        } else {
-            if s.starts_with("one") {
-                Some(1)
-            } else if s.starts_with("two") {
-                Some(2)
-            } else if s.starts_with("three") {
-                Some(3)
-            } else if s.starts_with("four") {
-                Some(4)
-            } else if s.starts_with("five") {
-                Some(5)
-            } else if s.starts_with("six") {
-                Some(6)
-            } else if s.starts_with("seven") {
-                Some(7)
-            } else if s.starts_with("eight") {
-                Some(8)
-            } else if s.starts_with("nine") {
-                Some(9)
-            } else {
-                None
+    /// With [`Words::Numbers`], the [`NUMBER_WORDS`] are read as well as
+    /// the dictionary's.
+    pub fn with_rules(dictionary: &Dictionary, digits: DigitPolicy, rules: Rules) -> Self {
+        let mut dictionary = dictionary.clone();
+        if rules.words == Words::Numbers {
+            for &(word, value) in NUMBER_WORDS {
+                // A dictionary giving one of these another value keeps it.
+                let _ = dictionary.insert(word, value);
             }
         }
+
+        Calibration {
+            digits,
+            rules,
+            words: dictionary.automaton(),
+            reversed: dictionary.reversed_automaton(),
+            spelled_out: !dictionary.is_empty(),
+        }
+    }
+
+    pub fn rules(&self) -> &Rules {
+        &self.rules
     }
 
-    let parsed_nums: Vec<Option<i32>> = line
-        .char_indices()
-        .map(|(index, _)| consume_number(line.get(index..)?))
-        .collect();
+    /// Sums the calibration value of every line.
+    pub fn sum(&self, input: &str) -> GenResult<u64> {
+        Ok(self.tally(input, false)?.sum)
+    }
 
-    let nums: Vec<i32> = parsed_nums.into_iter().flatten().collect();
+    /// Sums the lines' values, skipping lines without one if `lenient`.
+    pub fn tally(&self, input: &str, lenient: bool) -> GenResult<Tally> {
+        let mut tally = Tally::default();
+        for line in lines(input) {
+            tally.add(self.scan(&line), lenient)?;
+        }
 
-    let first = nums
-        .first()
-        .ok_or(format!("Could not find first digit (p2): {}", line))?;
+        Ok(tally)
+    }
 
-    let second = nums
-        .last()
-        .ok_or(format!("Could not find second digit (p2): {}", line))?;
+    /// Works out the line's value, keeping every token along the way.
+    pub fn explain(&self, line: &Line) -> GenResult<Explanation> {
+        let matches = self.matches(line)?;
+        let is_digit = |m: &Match| {
+            let mut chars = line.text[m.span.clone()].chars();
+            matches!(
+                (chars.next().map(|c| self.digits.classify(c)), chars.next()),
+                (Some(CharClass::Digit(_)), None)
+            )
+        };
+        let tokens = self.rules.tokens(line.text, matches, is_digit);
+
+        let (value, first, last) = self.rules.select(&tokens).ok_or_else(|| {
+            let expected = match tokens.is_empty() {
+                true => None,
+                false => self.rules.needed(),
+            };
+            line.error(line.text, expected.unwrap_or(self.expected().to_string()))
+        })?;
+
+        Ok(Explanation {
+            line: line.number,
+            matches: tokens,
+            first,
+            last,
+            value,
+        })
+    }
 
-    let res = format!("{}{}", first, second).parse::<i32>()?;
+    /// Every digit and word in the line, unordered.
+    pub fn matches(&self, line: &Line) -> GenResult<Vec<Match>> {
+        let mut matches = vec![];
+        for (i, c) in line.text.char_indices() {
+            let span = i..i + c.len_utf8();
+            match self.digits.classify(c) {
+                CharClass::Digit(value) => matches.push(Match { span, value }),
+                CharClass::Text => {}
+                CharClass::Rejected => {
+                    return Err(line.error_at(span, "an ASCII digit, not another numeral"))
+                }
+            }
+        }
+        matches.extend(self.words.find_overlapping(line.text));
 
-    Ok(res)
+        Ok(matches)
+    }
+
+    fn expected(&self) -> &'static str {
+        if self.spelled_out {
+            "a digit or spelled-out digit in the line"
+        } else {
+            "a digit in the line"
+        }
+    }
 }
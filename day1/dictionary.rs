@@ -0,0 +1,161 @@
+//! The spelled-out digits recognised in calibration lines: built-in word
+//! lists per language, and user-supplied files of `<word> <value>` lines.
+
+use crate::automaton::AhoCorasick;
+use aoc_core::{lines, read_input, GenResult};
+use std::path::Path;
+
+const ENGLISH: &[(&str, u32)] = &[
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+const FRENCH: &[(&str, u32)] = &[
+    ("un", 1),
+    ("deux", 2),
+    ("trois", 3),
+    ("quatre", 4),
+    ("cinq", 5),
+    ("six", 6),
+    ("sept", 7),
+    ("huit", 8),
+    ("neuf", 9),
+];
+
+const GERMAN: &[(&str, u32)] = &[
+    ("eins", 1),
+    ("zwei", 2),
+    ("drei", 3),
+    ("vier", 4),
+    ("fünf", 5),
+    ("sechs", 6),
+    ("sieben", 7),
+    ("acht", 8),
+    ("neun", 9),
+];
+
+const SPANISH: &[(&str, u32)] = &[
+    ("uno", 1),
+    ("dos", 2),
+    ("tres", 3),
+    ("cuatro", 4),
+    ("cinco", 5),
+    ("seis", 6),
+    ("siete", 7),
+    ("ocho", 8),
+    ("nueve", 9),
+];
+
+/// The built-in word lists by name.
+pub const LANGUAGES: &[(&str, &[(&str, u32)])] = &[
+    ("en", ENGLISH),
+    ("fr", FRENCH),
+    ("de", GERMAN),
+    ("es", SPANISH),
+];
+
+#[derive(Clone, Debug, Default)]
+pub struct Dictionary {
+    words: Vec<(String, u32)>,
+}
+
+impl Dictionary {
+    /// One of the [`LANGUAGES`].
+    pub fn builtin(language: &str) -> GenResult<Self> {
+        let (_, words) = LANGUAGES
+            .iter()
+            .find(|(name, _)| *name == language)
+            .ok_or_else(|| {
+                let names: Vec<_> = LANGUAGES.iter().map(|(name, _)| *name).collect();
+                format!(
+                    "Unknown language '{}', expected one of {}",
+                    language,
+                    names.join(", ")
+                )
+            })?;
+
+        Ok(Dictionary {
+            words: words.iter().map(|&(w, v)| (w.to_string(), v)).collect(),
+        })
+    }
+
+    /// Parses `<word> <value>` lines; blank lines and `#` comments are
+    /// ignored.
+    pub fn parse(text: &str) -> GenResult<Self> {
+        let mut dictionary = Dictionary::default();
+
+        for line in lines(text) {
+            let entry = line.text.trim();
+            if entry.is_empty() || entry.starts_with('#') {
+                continue;
+            }
+
+            let (word, value) = line.split_pair(entry, ' ')?;
+            let value = value.trim();
+            let digit = line.parse::<u32>(value, "a digit value from 0 to 9")?;
+            if digit > 9 {
+                return Err(line.error(value, "a digit value from 0 to 9"));
+            }
+            dictionary.insert(word, digit)?;
+        }
+
+        Ok(dictionary)
+    }
+
+    pub fn load(path: &Path) -> GenResult<Self> {
+        Dictionary::parse(&read_input(path)?).map_err(|e| e.with_path(path))
+    }
+
+    /// Adds `word`. Words shared between lists ("six" in English and
+    /// French) are fine as long as they agree on the value.
+    pub fn insert(&mut self, word: &str, value: u32) -> GenResult<()> {
+        match self.words.iter().find(|(w, _)| w == word) {
+            Some((_, existing)) if *existing != value => Err(format!(
+                "'{}' is both {} and {} across the chosen dictionaries",
+                word, existing, value
+            )
+            .into()),
+            Some(_) => Ok(()),
+            None => {
+                self.words.push((word.to_string(), value));
+                Ok(())
+            }
+        }
+    }
+
+    pub fn extend(&mut self, other: &Dictionary) -> GenResult<()> {
+        for (word, value) in &other.words {
+            self.insert(word, *value)?;
+        }
+        Ok(())
+    }
+
+    pub fn words(&self) -> &[(String, u32)] {
+        &self.words
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    pub fn automaton(&self) -> AhoCorasick {
+        AhoCorasick::new(self.words.iter().map(|(w, v)| (w.as_bytes(), *v)))
+    }
+
+    /// An automaton over the words spelled backwards, for scanning a line
+    /// from its end.
+    pub fn reversed_automaton(&self) -> AhoCorasick {
+        AhoCorasick::new(self.words.iter().map(|(w, v)| {
+            let mut reversed = w.as_bytes().to_vec();
+            reversed.reverse();
+            (reversed, *v)
+        }))
+    }
+}
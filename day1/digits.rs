@@ -0,0 +1,75 @@
+//! Which characters count as digits in a calibration line.
+
+use aoc_core::Error;
+use std::str::FromStr;
+
+/// The zero of every run of ten decimal digits (general category `Nd`) as
+/// of Unicode 15.1. Each script's digits are contiguous, zero to nine.
+const DECIMAL_ZEROS: &[u32] = &[
+    0x0030, 0x0660, 0x06F0, 0x07C0, 0x0966, 0x09E6, 0x0A66, 0x0AE6, 0x0B66, 0x0BE6, 0x0C66, 0x0CE6,
+    0x0D66, 0x0DE6, 0x0E50, 0x0ED0, 0x0F20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946, 0x19D0, 0x1A80,
+    0x1A90, 0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900, 0xA9D0, 0xA9F0, 0xAA50, 0xABF0,
+    0xFF10, 0x104A0, 0x10D30, 0x11066, 0x110F0, 0x11136, 0x111D0, 0x112F0, 0x11450, 0x114D0,
+    0x11650, 0x116C0, 0x11730, 0x118E0, 0x11950, 0x11C50, 0x11D50, 0x11DA0, 0x11F50, 0x16A60,
+    0x16AC0, 0x16B50, 0x1D7CE, 0x1D7D8, 0x1D7E2, 0x1D7EC, 0x1D7F6, 0x1E140, 0x1E2F0, 0x1E4F0,
+    0x1E950, 0x1FBF0,
+];
+
+/// The value of `c` if it is a decimal digit in any script.
+pub fn decimal_value(c: char) -> Option<u32> {
+    let c = c as u32;
+    let i = DECIMAL_ZEROS.partition_point(|&zero| zero <= c);
+    let zero = DECIMAL_ZEROS[i.checked_sub(1)?];
+    (c - zero < 10).then_some(c - zero)
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DigitPolicy {
+    /// Only `0`-`9` are digits; anything else is just text.
+    #[default]
+    Ascii,
+    /// Decimal digits from any script count, at their value ('٣' is 3).
+    Unicode,
+    /// Only `0`-`9` are digits, and any other numeric character ('٣', '½',
+    /// '²') is an error rather than being silently skipped.
+    Reject,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CharClass {
+    Digit(u32),
+    Text,
+    Rejected,
+}
+
+impl DigitPolicy {
+    pub fn classify(self, c: char) -> CharClass {
+        if let Some(value) = c.to_digit(10) {
+            return CharClass::Digit(value);
+        }
+
+        match self {
+            DigitPolicy::Ascii => CharClass::Text,
+            DigitPolicy::Unicode => decimal_value(c).map_or(CharClass::Text, CharClass::Digit),
+            DigitPolicy::Reject if c.is_numeric() => CharClass::Rejected,
+            DigitPolicy::Reject => CharClass::Text,
+        }
+    }
+}
+
+impl FromStr for DigitPolicy {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        match s {
+            "ascii" => Ok(DigitPolicy::Ascii),
+            "unicode" => Ok(DigitPolicy::Unicode),
+            "reject" => Ok(DigitPolicy::Reject),
+            _ => Err(format!(
+                "Unknown digit policy '{}', expected ascii, unicode or reject",
+                s
+            )
+            .into()),
+        }
+    }
+}
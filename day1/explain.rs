@@ -0,0 +1,116 @@
+//! Showing how a line's calibration value came about: every digit and word
+//! found, and which of them the first and last digits were taken from.
+
+use crate::automaton::Match;
+use std::fmt::Write;
+
+const FIRST: &str = "\x1b[1;32m";
+const LAST: &str = "\x1b[1;35m";
+const FIRST_AND_LAST: &str = "\x1b[1;33m";
+const OTHER: &str = "\x1b[36m";
+const RESET: &str = "\x1b[0m";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Explanation {
+    /// 1-based line number.
+    pub line: usize,
+    /// Every digit and word found, ordered by where they start. Under
+    /// [`Words::Numbers`](crate::Words::Numbers) a run of number words is one
+    /// match.
+    pub matches: Vec<Match>,
+    /// Indices into `matches` of those the first and last digits came from.
+    pub first: Vec<usize>,
+    pub last: Vec<usize>,
+    pub value: u64,
+}
+
+impl Explanation {
+    /// A header with the line and its value, then one row per match. With
+    /// `color`, the line is highlighted with ANSI escapes: the first digit
+    /// green, the last magenta, yellow where the two overlap, and other
+    /// matches cyan.
+    pub fn render(&self, text: &str, color: bool) -> String {
+        let shown = if color {
+            self.highlight(text)
+        } else {
+            text.to_string()
+        };
+        let mut out = format!("line {}: {} -> {}\n", self.line, shown, self.value);
+
+        let spans: Vec<String> = self
+            .matches
+            .iter()
+            .map(|m| format!("{}..{}", m.span.start, m.span.end))
+            .collect();
+        let words: Vec<String> = self
+            .matches
+            .iter()
+            .map(|m| format!("{:?}", &text[m.span.clone()]))
+            .collect();
+        let span_width = spans.iter().map(String::len).max().unwrap_or(0);
+        let word_width = words.iter().map(|w| w.chars().count()).max().unwrap_or(0);
+        let value_width = self
+            .matches
+            .iter()
+            .map(|m| m.value.to_string().len())
+            .max()
+            .unwrap_or(0);
+
+        for (i, m) in self.matches.iter().enumerate() {
+            let role = match (self.first.contains(&i), self.last.contains(&i)) {
+                (true, true) => "first, last",
+                (true, false) => "first",
+                (false, true) => "last",
+                (false, false) => "",
+            };
+            let row = format!(
+                "  {:<sw$}  {:<ww$}  {:>vw$}  {}",
+                spans[i],
+                words[i],
+                m.value,
+                role,
+                sw = span_width,
+                ww = word_width,
+                vw = value_width
+            );
+            let _ = writeln!(out, "{}", row.trim_end());
+        }
+
+        out
+    }
+
+    fn highlight(&self, text: &str) -> String {
+        let covers = |indices: &[usize], i: usize| {
+            indices.iter().any(|&m| self.matches[m].span.contains(&i))
+        };
+
+        let style_at = |i: usize| match (covers(&self.first, i), covers(&self.last, i)) {
+            (true, true) => Some(FIRST_AND_LAST),
+            (true, false) => Some(FIRST),
+            (false, true) => Some(LAST),
+            _ if self.matches.iter().any(|m| m.span.contains(&i)) => Some(OTHER),
+            _ => None,
+        };
+
+        let mut out = String::new();
+        let mut current = None;
+        for (i, c) in text.char_indices() {
+            let style = style_at(i);
+            if style != current {
+                if current.is_some() {
+                    out.push_str(RESET);
+                }
+                if let Some(style) = style {
+                    out.push_str(style);
+                }
+                current = style;
+            }
+            out.push(c);
+        }
+        if current.is_some() {
+            out.push_str(RESET);
+        }
+
+        out
+    }
+}
@@ -0,0 +1,370 @@
+//! The rules turning a line's digits and words into its calibration value:
+//! how many digits to take from each end, whether words may share letters
+//! and whether runs of number words form one number ("twenty three").
+
+use crate::automaton::Match;
+use aoc_core::Error;
+use std::cmp::Reverse;
+use std::ops::Range;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Words {
+    /// Every word is a digit on its own.
+    Digits,
+    /// Runs of number words separated by nothing, spaces or hyphens form a
+    /// single number, read in English ("one hundred", "twenty-three").
+    Numbers,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rules {
+    /// Digits taken from the start of the line.
+    pub first: usize,
+    /// Digits taken from the end of the line. The two may share digits, so
+    /// a line with a single `7` is 77.
+    pub last: usize,
+    pub words: Words,
+    /// Whether overlapping words both count ("eightwo" is 8 and 2). If not,
+    /// words are taken leftmost-longest and a word overlapping an earlier
+    /// one is dropped.
+    pub overlap: bool,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            first: 1,
+            last: 1,
+            words: Words::Digits,
+            overlap: true,
+        }
+    }
+}
+
+/// The named rule sets. Anything else is spelled out as
+/// `first=N,last=N,words=digits|numbers,overlap=yes|no`, starting from the
+/// defaults of `aoc`.
+pub const RULE_SETS: &[(&str, Rules)] = &[
+    (
+        "aoc",
+        Rules {
+            first: 1,
+            last: 1,
+            words: Words::Digits,
+            overlap: true,
+        },
+    ),
+    (
+        "no-overlap",
+        Rules {
+            first: 1,
+            last: 1,
+            words: Words::Digits,
+            overlap: false,
+        },
+    ),
+    (
+        "first2-last2",
+        Rules {
+            first: 2,
+            last: 2,
+            words: Words::Digits,
+            overlap: true,
+        },
+    ),
+    (
+        "numbers",
+        Rules {
+            first: 1,
+            last: 1,
+            words: Words::Numbers,
+            overlap: false,
+        },
+    ),
+];
+
+/// The words [`Words::Numbers`] reads in addition to the dictionary.
+pub const NUMBER_WORDS: &[(&str, u32)] = &[
+    ("zero", 0),
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+    ("ten", 10),
+    ("eleven", 11),
+    ("twelve", 12),
+    ("thirteen", 13),
+    ("fourteen", 14),
+    ("fifteen", 15),
+    ("sixteen", 16),
+    ("seventeen", 17),
+    ("eighteen", 18),
+    ("nineteen", 19),
+    ("twenty", 20),
+    ("thirty", 30),
+    ("forty", 40),
+    ("fifty", 50),
+    ("sixty", 60),
+    ("seventy", 70),
+    ("eighty", 80),
+    ("ninety", 90),
+    ("hundred", 100),
+    ("thousand", 1_000),
+    ("million", 1_000_000),
+];
+
+const MAX_DIGITS: usize = 9;
+
+impl Rules {
+    /// Plain first and last digit with overlapping words, which can be
+    /// found by scanning from both ends of a line.
+    pub fn is_first_last(&self) -> bool {
+        *self == Rules::default()
+    }
+
+    /// Turns the line's matches into the tokens that supply its digits:
+    /// sorted, with shadowed and (if not allowed) overlapping words removed
+    /// and, for [`Words::Numbers`], number words joined into numbers.
+    /// `is_digit` tells digit characters from words.
+    pub fn tokens(
+        &self,
+        text: &str,
+        mut matches: Vec<Match>,
+        is_digit: impl Fn(&Match) -> bool,
+    ) -> Vec<Match> {
+        // A word starting where a longer one does is never used.
+        matches.sort_by_key(|m| (m.span.start, Reverse(m.span.end)));
+        matches.dedup_by_key(|m| m.span.start);
+
+        if !self.overlap {
+            let mut end = 0;
+            matches.retain(|m| {
+                let keep = m.span.start >= end;
+                if keep {
+                    end = m.span.end;
+                }
+                keep
+            });
+        }
+
+        if self.words == Words::Numbers {
+            matches = join_numbers(text, matches, is_digit);
+        }
+
+        matches
+    }
+
+    /// Picks the first and last digits of `tokens`. Returns the value and
+    /// the indices of the tokens the first and the last digits came from,
+    /// or `None` when there are not enough digits.
+    pub fn select(&self, tokens: &[Match]) -> Option<(u64, Vec<usize>, Vec<usize>)> {
+        let mut digits: Vec<(char, usize)> = vec![];
+        for (i, token) in tokens.iter().enumerate() {
+            digits.extend(token.value.to_string().chars().map(|d| (d, i)));
+        }
+        if digits.is_empty() || digits.len() < self.first.max(self.last) {
+            return None;
+        }
+
+        let first = &digits[..self.first];
+        let last = &digits[digits.len() - self.last..];
+        let value: String = first.iter().chain(last).map(|&(d, _)| d).collect();
+        let sources = |digits: &[(char, usize)]| {
+            let mut indices: Vec<usize> = digits.iter().map(|&(_, i)| i).collect();
+            indices.dedup();
+            indices
+        };
+
+        Some((value.parse().ok()?, sources(first), sources(last)))
+    }
+
+    /// Describes what a line without enough digits is missing.
+    pub fn needed(&self) -> Option<String> {
+        match self.first.max(self.last) {
+            0 | 1 => None,
+            n => Some(format!("at least {} digits in the line", n)),
+        }
+    }
+}
+
+impl FromStr for Rules {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        if let Some((_, rules)) = RULE_SETS.iter().find(|(name, _)| *name == s) {
+            return Ok(*rules);
+        }
+        if !s.contains('=') {
+            let names: Vec<_> = RULE_SETS.iter().map(|(name, _)| *name).collect();
+            return Err(format!(
+                "Unknown rule set '{}', expected one of {} or first=N,last=N,words=digits|numbers,overlap=yes|no",
+                s,
+                names.join(", ")
+            )
+            .into());
+        }
+
+        let mut rules = Rules::default();
+        for setting in s.split(',') {
+            let (key, value) = setting.split_once('=').ok_or(format!(
+                "Expected '<key>=<value>' in rules, found '{}'",
+                setting
+            ))?;
+            match (key.trim(), value.trim()) {
+                ("first", n) => rules.first = n.parse()?,
+                ("last", n) => rules.last = n.parse()?,
+                ("words", "digits") => rules.words = Words::Digits,
+                ("words", "numbers") => rules.words = Words::Numbers,
+                ("overlap", "yes") => rules.overlap = true,
+                ("overlap", "no") => rules.overlap = false,
+                _ => return Err(format!("Unknown rule setting '{}'", setting).into()),
+            }
+        }
+
+        if rules.first + rules.last == 0 || rules.first.max(rules.last) > MAX_DIGITS {
+            return Err(format!(
+                "Rules must take between 1 and {} digits from each end",
+                MAX_DIGITS
+            )
+            .into());
+        }
+        Ok(rules)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Kind {
+    Zero,
+    Unit,
+    Teen,
+    Tens,
+    Hundred,
+    Scale,
+}
+
+fn kind(value: u32) -> Kind {
+    match value {
+        0 => Kind::Zero,
+        1..=9 => Kind::Unit,
+        10..=19 => Kind::Teen,
+        100 => Kind::Hundred,
+        v if v < 100 => Kind::Tens,
+        _ => Kind::Scale,
+    }
+}
+
+/// A number being read from a run of words.
+struct Number {
+    span: Range<usize>,
+    /// Thousands and millions already complete.
+    total: u32,
+    /// The part below the last scale word.
+    current: u32,
+    last: Kind,
+    /// The last scale word used; later ones must be smaller.
+    scale: u32,
+}
+
+impl Number {
+    fn start(word: &Match) -> Self {
+        let (total, current, scale) = match kind(word.value) {
+            Kind::Scale => (word.value, 0, word.value),
+            _ => (0, word.value, u32::MAX),
+        };
+        Number {
+            span: word.span.clone(),
+            total,
+            current,
+            last: kind(word.value),
+            scale,
+        }
+    }
+
+    /// Adds `word` to the number if the grammar allows it there.
+    fn push(&mut self, word: &Match) -> bool {
+        let value = word.value;
+        let next = kind(value);
+        let fits = match next {
+            Kind::Zero => false,
+            Kind::Unit => matches!(self.last, Kind::Tens | Kind::Hundred | Kind::Scale),
+            Kind::Teen | Kind::Tens => matches!(self.last, Kind::Hundred | Kind::Scale),
+            Kind::Hundred => matches!(self.last, Kind::Unit | Kind::Teen) && self.current < 100,
+            Kind::Scale => {
+                !matches!(self.last, Kind::Zero | Kind::Scale)
+                    && value < self.scale
+                    && self.current > 0
+            }
+        };
+        if !fits {
+            return false;
+        }
+
+        let (total, current) = match next {
+            Kind::Hundred => (Some(self.total), self.current.checked_mul(100)),
+            Kind::Scale => (
+                self.current
+                    .checked_mul(value)
+                    .and_then(|v| self.total.checked_add(v)),
+                Some(0),
+            ),
+            _ => (Some(self.total), self.current.checked_add(value)),
+        };
+        // A word that would take the number past `u32::MAX` starts a new one.
+        let Some((total, current)) = total
+            .zip(current)
+            .filter(|&(total, current)| total.checked_add(current).is_some())
+        else {
+            return false;
+        };
+
+        self.total = total;
+        self.current = current;
+        if next == Kind::Scale {
+            self.scale = value;
+        }
+        self.last = next;
+        self.span.end = word.span.end;
+        true
+    }
+
+    fn finish(self) -> Match {
+        Match {
+            span: self.span,
+            value: self.total + self.current,
+        }
+    }
+}
+
+/// Joins runs of adjacent number words into single numbers. Words are
+/// adjacent if only spaces and hyphens (or nothing) lie between them.
+fn join_numbers(text: &str, tokens: Vec<Match>, is_digit: impl Fn(&Match) -> bool) -> Vec<Match> {
+    let mut joined = vec![];
+    let mut number: Option<Number> = None;
+
+    for token in tokens {
+        if is_digit(&token) {
+            joined.extend(number.take().map(Number::finish));
+            joined.push(token);
+            continue;
+        }
+
+        if let Some(n) = &mut number {
+            let between = text.get(n.span.end..token.span.start).unwrap_or("");
+            let adjacent =
+                token.span.start >= n.span.end && between.chars().all(|c| c == ' ' || c == '-');
+            if adjacent && n.push(&token) {
+                continue;
+            }
+        }
+        joined.extend(number.replace(Number::start(&token)).map(Number::finish));
+    }
+    joined.extend(number.map(Number::finish));
+
+    joined
+}
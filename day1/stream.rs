@@ -0,0 +1,238 @@
+//! Calibrating documents too large to hold in memory. Only one line is held
+//! at a time, and files can be split into chunks summed on separate
+//! threads. Each line is scanned inward from the front until its first digit
+//! is certain and from the back until its last one is, which saves matching
+//! words across its middle; reading and UTF-8 checking still cover all of
+//! it, as does looking for rejected numerals under `DigitPolicy::Reject`.
+
+use crate::digits::{CharClass, DigitPolicy};
+use crate::{Calibration, Tally};
+use aoc_core::{Error, GenResult, Line, ParseError};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::ops::Range;
+use std::path::Path;
+use std::str::Utf8Error;
+use std::thread;
+
+impl Calibration {
+    /// The line's calibration value. Under the default rules it is found
+    /// without collecting the line's matches.
+    pub fn scan(&self, line: &Line) -> GenResult<u64> {
+        if !self.rules.is_first_last() {
+            return Ok(self.explain(line)?.value);
+        }
+
+        if self.digits == DigitPolicy::Reject {
+            // A rejected numeral anywhere in the line is an error, so this
+            // policy has to look at the whole line.
+            if let Some((i, c)) = line
+                .text
+                .char_indices()
+                .find(|&(_, c)| self.digits.classify(c) == CharClass::Rejected)
+            {
+                return Err(
+                    line.error_at(i..i + c.len_utf8(), "an ASCII digit, not another numeral")
+                );
+            }
+        }
+
+        let first = self.first_digit(line.text);
+        let last = self.last_digit(line.text);
+        match first.zip(last) {
+            Some((first, last)) => Ok((first * 10 + last) as u64),
+            None => Err(line.error(line.text, self.expected())),
+        }
+    }
+
+    /// The leftmost match, the longest if several start there. A match is
+    /// reported once its end is read, so scanning carries on until no word
+    /// starting further left could still end.
+    fn first_digit(&self, text: &str) -> Option<u32> {
+        let horizon = self.words.max_len().max(1);
+        let mut best: Option<(Range<usize>, u32)> = None;
+
+        let mut state = 0;
+        for (i, c) in text.char_indices() {
+            if best
+                .as_ref()
+                .is_some_and(|(span, _)| i >= span.start + horizon)
+            {
+                break;
+            }
+
+            if let CharClass::Digit(value) = self.digits.classify(c) {
+                keep_leftmost(&mut best, i..i + c.len_utf8(), value);
+            }
+            let bytes = &text.as_bytes()[i..i + c.len_utf8()];
+            for (j, &b) in bytes.iter().enumerate() {
+                state = self.words.step(state, b);
+                let end = i + j + 1;
+                for (len, value) in self.words.words_at(state) {
+                    keep_leftmost(&mut best, end - len..end, value);
+                }
+            }
+        }
+
+        best.map(|(_, value)| value)
+    }
+
+    /// The rightmost match, the longest if several start there. Reading the
+    /// line backwards with the words reversed reports each match as its
+    /// start is read, so the first character with a match settles it.
+    fn last_digit(&self, text: &str) -> Option<u32> {
+        let mut state = 0;
+        for (i, c) in text.char_indices().rev() {
+            let mut best: Option<(usize, u32)> = None;
+
+            if let CharClass::Digit(value) = self.digits.classify(c) {
+                best = Some((c.len_utf8(), value));
+            }
+            for &b in text.as_bytes()[i..i + c.len_utf8()].iter().rev() {
+                state = self.reversed.step(state, b);
+            }
+            // Words starting at this character are reported once all of its
+            // bytes have been read.
+            for (len, value) in self.reversed.words_at(state) {
+                if best.is_none_or(|(l, _)| len > l) {
+                    best = Some((len, value));
+                }
+            }
+
+            if let Some((_, value)) = best {
+                return Some(value);
+            }
+        }
+
+        None
+    }
+
+    /// Sums a document line by line, holding only the current line.
+    pub fn sum_reader(&self, reader: impl BufRead) -> GenResult<u64> {
+        Ok(self.tally_reader(reader, false)?.sum)
+    }
+
+    /// [`Calibration::sum_reader`], skipping lines without a value if
+    /// `lenient`.
+    pub fn tally_reader(&self, reader: impl BufRead, lenient: bool) -> GenResult<Tally> {
+        self.sum_lines(reader, 0, u64::MAX, lenient)
+    }
+
+    /// Sums the file at `path` on up to `threads` threads, each taking an
+    /// equal share of its bytes.
+    pub fn sum_file(&self, path: &Path, threads: usize) -> GenResult<u64> {
+        Ok(self.tally_file(path, threads, false)?.sum)
+    }
+
+    /// [`Calibration::sum_file`], skipping lines without a value if
+    /// `lenient`.
+    pub fn tally_file(&self, path: &Path, threads: usize, lenient: bool) -> GenResult<Tally> {
+        let with_path = |e: Error| e.with_path(path);
+        let len = File::open(path)
+            .and_then(|f| f.metadata())
+            .map_err(|e| with_path(e.into()))?
+            .len();
+        let threads = (threads.max(1) as u64).min(len.max(1));
+        let bounds: Vec<u64> = (0..=threads).map(|i| len * i / threads).collect();
+
+        let results: Vec<GenResult<Tally>> = thread::scope(|scope| {
+            let handles: Vec<_> = bounds
+                .windows(2)
+                .map(|range| {
+                    let (start, end) = (range[0], range[1]);
+                    scope.spawn(move || self.sum_chunk(path, start, end, lenient))
+                })
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+
+        // Chunks only know their own line numbers; shift errors by the
+        // lines in the chunks before them.
+        let mut tally = Tally::default();
+        for result in results {
+            match result {
+                Ok(chunk) => tally.append(chunk).map_err(with_path)?,
+                Err(Error::Parse(mut e)) => {
+                    e.line += tally.lines;
+                    return Err(with_path(Error::Parse(e)));
+                }
+                Err(e) => return Err(with_path(e)),
+            }
+        }
+        for e in &mut tally.skipped {
+            e.path = Some(path.to_path_buf());
+        }
+
+        Ok(tally)
+    }
+
+    /// Sums the lines starting in `start..end` of the file. A line belongs
+    /// to the chunk it starts in, however far past `end` it runs.
+    fn sum_chunk(&self, path: &Path, start: u64, end: u64, lenient: bool) -> GenResult<Tally> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut offset = 0;
+        if start > 0 {
+            // Lines start just after a newline, which may be `start - 1`.
+            reader.seek(SeekFrom::Start(start - 1))?;
+            offset = start - 1 + reader.skip_until(b'\n')? as u64;
+        }
+
+        self.sum_lines(reader, offset, end, lenient)
+    }
+
+    /// Sums lines from `reader`, which is at byte `offset`, stopping before
+    /// the first line to start at or past `end`.
+    fn sum_lines(
+        &self,
+        mut reader: impl BufRead,
+        mut offset: u64,
+        end: u64,
+        lenient: bool,
+    ) -> GenResult<Tally> {
+        let mut buf = vec![];
+        let mut tally = Tally::default();
+        while offset < end {
+            buf.clear();
+            let read = reader.read_until(b'\n', &mut buf)?;
+            if read == 0 {
+                break;
+            }
+            offset += read as u64;
+
+            let number = tally.lines + 1;
+            let value = match std::str::from_utf8(&buf) {
+                Ok(text) => {
+                    let text = text.strip_suffix('\n').unwrap_or(text);
+                    let text = text.strip_suffix('\r').unwrap_or(text);
+                    self.scan(&Line { number, text })
+                }
+                Err(e) => Err(not_utf8(number, &buf, e)),
+            };
+            tally.add(value, lenient)?;
+        }
+
+        Ok(tally)
+    }
+}
+
+fn keep_leftmost(best: &mut Option<(Range<usize>, u32)>, span: Range<usize>, value: u32) {
+    let better = best
+        .as_ref()
+        .is_none_or(|(b, _)| span.start < b.start || (span.start == b.start && span.end > b.end));
+    if better {
+        *best = Some((span, value));
+    }
+}
+
+fn not_utf8(number: usize, bytes: &[u8], e: Utf8Error) -> Error {
+    let source_line = String::from_utf8_lossy(bytes).trim_end().to_string();
+    // The lossy copy matches up to the bad byte, which becomes U+FFFD.
+    let at = e.valid_up_to();
+    Error::Parse(ParseError {
+        path: None,
+        line: number,
+        span: at..at + '\u{fffd}'.len_utf8(),
+        expected: "valid UTF-8".to_string(),
+        source_line,
+    })
+}
@@ -0,0 +1,55 @@
+//! Sums that carry on past lines without a value, remembering which lines
+//! were left out and why.
+
+use aoc_core::{Error, GenResult, ParseError};
+use std::fmt::Write;
+
+/// A document's sum. In lenient mode a line that cannot be read is skipped
+/// and its error kept here instead of ending the sum.
+#[derive(Debug, Default)]
+pub struct Tally {
+    pub sum: u64,
+    /// Lines read, including skipped ones.
+    pub lines: usize,
+    pub skipped: Vec<ParseError>,
+}
+
+impl Tally {
+    /// Counts a line's value, or with `lenient` the reason it has none.
+    /// Errors other than parse errors always end the sum.
+    pub fn add(&mut self, value: GenResult<u64>, lenient: bool) -> GenResult<()> {
+        self.lines += 1;
+        match value {
+            Ok(value) => self.sum = checked_sum(self.sum, value)?,
+            Err(Error::Parse(e)) if lenient => self.skipped.push(e),
+            Err(e) => return Err(e),
+        }
+        Ok(())
+    }
+
+    /// Appends the tally of the lines after these ones.
+    pub(crate) fn append(&mut self, mut later: Tally) -> GenResult<()> {
+        for e in &mut later.skipped {
+            e.line += self.lines;
+        }
+        self.sum = checked_sum(self.sum, later.sum)?;
+        self.lines += later.lines;
+        self.skipped.append(&mut later.skipped);
+        Ok(())
+    }
+
+    /// One line per skipped line with what it was missing, after a count.
+    pub fn report(&self) -> String {
+        let mut out = format!("skipped {} of {} lines\n", self.skipped.len(), self.lines);
+        for e in &self.skipped {
+            let _ = writeln!(out, "  line {}: expected {}", e.line, e.expected);
+        }
+        out
+    }
+}
+
+/// An overflow is not a line's fault, so it is never skipped as one.
+fn checked_sum(sum: u64, value: u64) -> GenResult<u64> {
+    sum.checked_add(value)
+        .ok_or_else(|| "The calibration values add up to more than a u64 holds".into())
+}
@@ -1,127 +1,348 @@
-use std::error::Error;
-use std::fs::read_to_string;
+mod infer;
+mod query;
+mod report;
 
-fn read_lines() -> Result<Vec<String>, Box<dyn Error>> {
-    Ok(read_to_string("input.txt")?
-        .lines()
-        .map(String::from)
-        .collect())
-}
+pub use infer::{log_likelihood, most_likely_bag, round_log_likelihood, Estimate};
+pub use query::{describe, first_offense, load_bag, parse_bag, query, smallest_bag, Offense};
+pub use report::{sort_summaries, summarize, Column, Summary};
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let input = read_lines()?;
+use aoc_core::{lines, Error, GenResult, Line, Solution};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::str::FromStr;
 
-    part1(&input)?;
-    part2(&input)?;
+pub struct Day2;
 
-    Ok(())
-}
+impl Solution for Day2 {
+    type Parsed = Vec<Game>;
+    type Answer1 = usize;
+    type Answer2 = i64;
 
-fn part1(input: &Vec<String>) -> Result<(), Box<dyn Error>> {
-    let mut sum = 0;
-    for (i, line) in input.iter().enumerate() {
-        let game_num = i + 1;
+    fn parse(input: &str) -> GenResult<Vec<Game>> {
+        parse_games(input, &ParseOptions::default())
+    }
 
-        let valid_game = is_valid_game(&line)?;
+    fn part1(games: &Vec<Game>) -> GenResult<usize> {
+        let bag = elf_bag();
+        id_sum(
+            games
+                .iter()
+                .filter(|game| is_valid_game(&game.rounds, &bag)),
+        )
+    }
 
-        if valid_game {
-            sum += game_num;
+    fn part2(games: &Vec<Game>) -> GenResult<i64> {
+        let colors = Palette::default().colors(games);
+        let mut sum: i64 = 0;
+        for game in games {
+            let power = game_power(&game.rounds, &colors)
+                .map_err(|e| format!("Game {}: {}", game.id, e))?;
+            sum = sum
+                .checked_add(power)
+                .ok_or("The sum of the games' powers overflows an i64")?;
         }
+
+        Ok(sum)
     }
+}
+
+/// Cube counts by color: the cubes drawn in a round, or a bag's contents.
+pub type Cubes = BTreeMap<String, i32>;
 
-    println!("part 1: {}", sum);
+/// The bag of the puzzle: 12 red, 13 green and 14 blue cubes.
+pub fn elf_bag() -> Cubes {
+    [("red", 12), ("green", 13), ("blue", 14)]
+        .into_iter()
+        .map(|(color, n)| (color.to_string(), n))
+        .collect()
+}
 
-    Ok(())
+/// The cube colors a log may use.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Palette {
+    /// Only these colors; any other is a parse error.
+    Known(Vec<String>),
+    /// Whatever colors the log uses.
+    Open,
 }
 
-fn part2(input: &Vec<String>) -> Result<(), Box<dyn Error>> {
-    let mut sum = 0;
-    for line in input.iter() {
-        sum += game_power(&line)?;
+impl Default for Palette {
+    fn default() -> Self {
+        Palette::Known(vec!["red".into(), "green".into(), "blue".into()])
     }
+}
 
-    println!("part 2: {}", sum);
+impl Palette {
+    /// The colors in play: the known ones, or for an open palette every
+    /// color the games use, sorted.
+    pub fn colors(&self, games: &[Game]) -> Vec<String> {
+        match self {
+            Palette::Known(colors) => colors.clone(),
+            Palette::Open => {
+                let seen: BTreeSet<&String> = games
+                    .iter()
+                    .flat_map(|game| &game.rounds)
+                    .flat_map(|round| round.keys())
+                    .collect();
+                seen.into_iter().cloned().collect()
+            }
+        }
+    }
 
-    Ok(())
+    fn expected(colors: &[String]) -> String {
+        match colors {
+            [] => "no color".to_string(),
+            [only] => only.clone(),
+            [init @ .., last] => format!("{} or {}", init.join(", "), last),
+        }
+    }
+}
+
+/// `open`, or the known colors separated by commas.
+impl FromStr for Palette {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        if s == "open" {
+            return Ok(Palette::Open);
+        }
+
+        let mut colors: Vec<String> = vec![];
+        for color in s.split(',').map(str::trim) {
+            if color.is_empty() || colors.iter().any(|c| c == color) {
+                return Err(format!(
+                    "Expected 'open' or distinct colors separated by commas, found '{}'",
+                    s
+                )
+                .into());
+            }
+            colors.push(color.to_string());
+        }
+        Ok(Palette::Known(colors))
+    }
 }
 
 #[derive(Debug)]
-struct Round {
-    red: i32,
-    green: i32,
-    blue: i32,
+pub struct Game {
+    /// The `N` of the `Game N:` header.
+    pub id: usize,
+    /// 1-based line number in the log.
+    pub line: usize,
+    pub rounds: Vec<Cubes>,
 }
 
-fn parse_game(game_str: &str) -> Result<Vec<Round>, Box<dyn Error>> {
-    let (_, rounds_str) = game_str
-        .split_once(':')
-        .ok_or(format!("Could not get rounds from game: {}", game_str))?;
+/// How strictly a game log is read.
+#[derive(Clone, Debug, Default)]
+pub struct ParseOptions {
+    pub palette: Palette,
+    /// Add up a color drawn twice in one round ("3 red, 4 red" is 7 red)
+    /// rather than reject the round.
+    pub merge: bool,
+}
 
-    rounds_str
-        .trim()
-        .split(';')
-        .map(|s| parse_round(s.trim()))
-        .collect()
+/// Parses a game log. Each game keeps the ID from its header, so a log
+/// with games left out or reordered still gives the right answers; an ID
+/// used twice is an error, as are empty rounds, counts below 1 and (unless
+/// merging) a color drawn twice in a round.
+pub fn parse_games(input: &str, options: &ParseOptions) -> GenResult<Vec<Game>> {
+    let mut seen: HashMap<usize, usize> = HashMap::new();
+    let mut games = vec![];
+
+    for line in lines(input) {
+        let (game, id_str) = parse_game(&line, options)?;
+        if let Some(first) = seen.insert(game.id, game.line) {
+            return Err(line.error(
+                id_str,
+                format!("a game ID not already used on line {}", first),
+            ));
+        }
+        games.push(game);
+    }
+
+    Ok(games)
 }
 
-fn parse_round(round_str: &str) -> Result<Round, Box<dyn Error>> {
-    let mut round = Round {
-        red: 0,
-        blue: 0,
-        green: 0,
+/// Parses a line, returning the game and the slice holding its ID.
+fn parse_game<'a>(line: &Line<'a>, options: &ParseOptions) -> GenResult<(Game, &'a str)> {
+    let (header, rounds_str) = line.split_pair(line.text, ':')?;
+    let id_str = header
+        .strip_prefix("Game ")
+        .ok_or_else(|| line.error(header, "'Game <id>'"))?;
+    let id = line.parse::<usize>(id_str, "a game ID")?;
+    if id == 0 {
+        return Err(line.error(id_str, "a game ID of at least 1"));
+    }
+
+    let rounds = rounds_str
+        .split(';')
+        .enumerate()
+        .map(|(i, s)| {
+            let at = format!("in game {}, round {}", id, i + 1);
+            parse_round(line, s, &at, options)
+        })
+        .collect::<GenResult<_>>()?;
+
+    let game = Game {
+        id,
+        line: line.number,
+        rounds,
     };
+    Ok((game, id_str))
+}
+
+/// Where a log's IDs depart from 1, 2, 3, ... in order. Neither changes
+/// the answers, but either can mean the log was filtered or shuffled.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IdIssue {
+    /// No game has an ID in `from..=to`, though a game with a higher one
+    /// exists.
+    Missing { from: usize, to: usize },
+    /// The game on `line` has a lower ID than the one before it.
+    OutOfOrder {
+        id: usize,
+        line: usize,
+        after: usize,
+    },
+}
+
+impl fmt::Display for IdIssue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IdIssue::Missing { from, to } if from == to => write!(f, "game {} is missing", from),
+            IdIssue::Missing { from, to } => write!(f, "games {} to {} are missing", from, to),
+            IdIssue::OutOfOrder { id, line, after } => {
+                write!(f, "line {}: game {} comes after game {}", line, id, after)
+            }
+        }
+    }
+}
+
+/// Checks that the games are numbered 1, 2, 3, ... in order.
+pub fn check_ids(games: &[Game]) -> Vec<IdIssue> {
+    let mut issues = vec![];
+
+    for pair in games.windows(2) {
+        if pair[1].id < pair[0].id {
+            issues.push(IdIssue::OutOfOrder {
+                id: pair[1].id,
+                line: pair[1].line,
+                after: pair[0].id,
+            });
+        }
+    }
+
+    let mut ids: Vec<usize> = games.iter().map(|g| g.id).collect();
+    ids.sort_unstable();
+    let mut expected = 1;
+    for id in ids {
+        if id > expected {
+            issues.push(IdIssue::Missing {
+                from: expected,
+                to: id - 1,
+            });
+        }
+        // An ID of usize::MAX leaves nothing after it to go missing.
+        expected = id.saturating_add(1);
+    }
+
+    issues
+}
+
+/// The sum of the games' IDs, or an error if it overflows a usize.
+pub fn id_sum<'a>(games: impl IntoIterator<Item = &'a Game>) -> GenResult<usize> {
+    games
+        .into_iter()
+        .try_fold(0usize, |sum, game| sum.checked_add(game.id))
+        .ok_or_else(|| "The game IDs add up to more than a usize holds".into())
+}
+
+/// Parses the draws of one round; `at` names the round in errors.
+fn parse_round(line: &Line, round_str: &str, at: &str, options: &ParseOptions) -> GenResult<Cubes> {
+    let mut round = Cubes::new();
+    if round_str.trim().is_empty() {
+        let start = line.span_of(round_str).start;
+        let span = start..start + round_str.len().max(1);
+        return Err(line.error_at(span, format!("at least one draw {}", at)));
+    }
 
     for cubes_str in round_str.split(',') {
         let trimmed = cubes_str.trim();
         let (count_str, color_str) = trimmed
             .split_once(' ')
-            .ok_or(format!("Could not split cubes_str: {}", cubes_str))?;
+            .filter(|(_, color)| !color.trim().is_empty())
+            .ok_or_else(|| line.error(trimmed, format!("'<count> <color>' {}", at)))?;
 
-        let count = count_str.parse::<i32>()?;
+        let count = line.parse::<i32>(count_str, "a cube count")?;
+        if count < 1 {
+            return Err(line.error(count_str, format!("a cube count of at least 1 {}", at)));
+        }
 
-        match color_str {
-            "red" => round.red = count,
-            "green" => round.green = count,
-            "blue" => round.blue = count,
-            _ => return Err(format!("Invalid cube color in round: {}", round_str).into()),
+        let color = color_str.trim();
+        if let Palette::Known(colors) = &options.palette {
+            if !colors.iter().any(|c| c == color) {
+                return Err(line.error(color, Palette::expected(colors)));
+            }
+        }
+        match round.get_mut(color) {
+            Some(total) if options.merge => {
+                *total = total.checked_add(count).ok_or_else(|| {
+                    line.error(
+                        color,
+                        format!("{} counts adding up to at most {} {}", color, i32::MAX, at),
+                    )
+                })?;
+            }
+            Some(_) => {
+                return Err(line.error(color, format!("a color not already drawn {}", at)));
+            }
+            None => {
+                round.insert(color.to_string(), count);
+            }
         }
     }
 
     Ok(round)
 }
 
-fn is_valid_game(line: &str) -> Result<bool, Box<dyn Error>> {
-    let parsed_rounds = parse_game(line)?;
+/// Whether every round could have been drawn from `bag`. Colors the bag
+/// lacks have no cubes.
+pub fn is_valid_game(rounds: &[Cubes], bag: &Cubes) -> bool {
+    rounds.iter().all(|round| fits(round, bag))
+}
+
+/// Whether `round` could have been drawn from `bag`.
+pub fn fits(round: &Cubes, bag: &Cubes) -> bool {
+    round
+        .iter()
+        .all(|(color, &n)| n <= bag.get(color).copied().unwrap_or(0))
+}
 
-    for round in parsed_rounds {
-        if round.red > 12 || round.green > 13 || round.blue > 14 {
-            return Ok(false);
+/// The fewest cubes of each color the game could have been played with:
+/// the most of it drawn in any one round.
+pub fn min_bag(rounds: &[Cubes]) -> Cubes {
+    let mut bag = Cubes::new();
+    for round in rounds {
+        for (color, &n) in round {
+            let most = bag.entry(color.clone()).or_insert(0);
+            *most = (*most).max(n);
         }
     }
 
-    Ok(true)
+    bag
 }
 
-fn game_power(line: &str) -> Result<i32, Box<dyn Error>> {
-    let parsed_rounds = parse_game(line)?;
-
-    let mut max_round = Round {
-        red: 0,
-        green: 0,
-        blue: 0,
-    };
-
-    for round in parsed_rounds {
-        if round.red > max_round.red {
-            max_round.red = round.red;
-        }
-        if round.green > max_round.green {
-            max_round.green = round.green;
-        }
-        if round.blue > max_round.blue {
-            max_round.blue = round.blue;
-        }
-    }
+/// The product of [`min_bag`] over `colors`; a color never drawn makes it
+/// zero.
+pub fn game_power(rounds: &[Cubes], colors: &[String]) -> GenResult<i64> {
+    power(&min_bag(rounds), colors)
+}
 
-    Ok(max_round.red * max_round.green * max_round.blue)
+/// The product of the bag's counts of `colors`, or an error if it overflows
+/// an i64.
+pub fn power(bag: &Cubes, colors: &[String]) -> GenResult<i64> {
+    colors
+        .iter()
+        .map(|color| bag.get(color).copied().unwrap_or(0) as i64)
+        .try_fold(1i64, |product, n| product.checked_mul(n))
+        .ok_or_else(|| format!("the power of {} overflows an i64", describe(bag, colors)).into())
 }
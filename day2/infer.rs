@@ -0,0 +1,141 @@
+//! Which bag most plausibly produced a log. Each round is taken to draw its
+//! cubes at random without replacement, and the cubes go back in the bag
+//! before the next round, so a round's chance is multivariate
+//! hypergeometric: the ways to pick the drawn cubes of each color over the
+//! ways to pick that many cubes from the whole bag.
+
+use crate::{Cubes, Game};
+use aoc_core::GenResult;
+use std::collections::BTreeMap;
+
+/// ln C(n, k), or minus infinity when `k` is out of `0..=n`.
+fn ln_choose(n: i64, k: i64) -> f64 {
+    if k < 0 || k > n {
+        return f64::NEG_INFINITY;
+    }
+    (0..k).map(|i| ((n - i) as f64 / (k - i) as f64).ln()).sum()
+}
+
+/// The log of the chance of drawing `round` from `bag`. Minus infinity if
+/// the bag cannot supply it.
+pub fn round_log_likelihood(round: &Cubes, bag: &Cubes) -> f64 {
+    let total: i64 = bag.values().map(|&n| n as i64).sum();
+    let drawn: i64 = round.values().map(|&n| n as i64).sum();
+    let ways: f64 = round
+        .iter()
+        .map(|(color, &n)| ln_choose(bag.get(color).copied().unwrap_or(0) as i64, n as i64))
+        .sum();
+
+    ways - ln_choose(total, drawn)
+}
+
+/// The log of the chance of a game's rounds under `bag`.
+pub fn log_likelihood(rounds: &[Cubes], bag: &Cubes) -> f64 {
+    rounds
+        .iter()
+        .map(|round| round_log_likelihood(round, bag))
+        .sum()
+}
+
+/// The most likely bag and how likely the log is under it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Estimate {
+    pub bag: Cubes,
+    pub log_likelihood: f64,
+    /// The likelihood was still rising at the largest bag tried, so a
+    /// bigger bag may be more likely still.
+    pub at_limit: bool,
+}
+
+/// The bag of `colors` with at most `max_cubes` cubes under which the
+/// games are most likely; the smallest such bag if several tie.
+///
+/// For a fixed number of cubes the log-likelihood is a sum of one concave
+/// term per color, so adding cubes one at a time to the color that gains
+/// most gives the best bag of every size in a single pass.
+pub fn most_likely_bag(games: &[Game], colors: &[String], max_cubes: usize) -> GenResult<Estimate> {
+    let rounds: Vec<&Cubes> = games.iter().flat_map(|g| &g.rounds).collect();
+
+    // How often each count of a color, and each round size, was drawn.
+    let mut draws: Vec<BTreeMap<i64, f64>> = vec![BTreeMap::new(); colors.len()];
+    let mut sizes: BTreeMap<i64, f64> = BTreeMap::new();
+    for round in &rounds {
+        for (c, color) in colors.iter().enumerate() {
+            if let Some(&n) = round.get(color) {
+                *draws[c].entry(n as i64).or_default() += 1.0;
+            }
+        }
+        *sizes
+            .entry(round.values().map(|&n| n as i64).sum())
+            .or_default() += 1.0;
+    }
+    if let Some(color) = rounds
+        .iter()
+        .flat_map(|round| round.keys())
+        .find(|color| !colors.contains(color))
+    {
+        return Err(format!("The log draws {} cubes, which the bag cannot hold", color).into());
+    }
+
+    // Going from `n` to `n + 1` cubes multiplies C(n, k) by
+    // (n + 1) / (n + 1 - k).
+    let gain = |counts: &BTreeMap<i64, f64>, n: i64| -> f64 {
+        counts
+            .iter()
+            .map(|(&k, &times)| times * ((n + 1) as f64 / (n + 1 - k) as f64).ln())
+            .sum()
+    };
+
+    let mut bag: Vec<i64> = draws
+        .iter()
+        .map(|counts| counts.keys().next_back().copied().unwrap_or(0))
+        .collect();
+    let smallest: i64 = bag.iter().sum();
+    if smallest > max_cubes as i64 {
+        return Err(format!(
+            "The log needs at least {} cubes, more than the limit of {}",
+            smallest, max_cubes
+        )
+        .into());
+    }
+
+    let as_cubes = |bag: &[i64]| -> Cubes {
+        colors
+            .iter()
+            .zip(bag)
+            .map(|(color, &n)| (color.clone(), n as i32))
+            .collect()
+    };
+
+    // Log-likelihoods relative to the smallest bag, which is all that
+    // comparing bags needs.
+    let mut relative = 0.0;
+    let mut best = (0.0, bag.clone());
+    for total in smallest..max_cubes as i64 {
+        let Some((c, color_gain)) = draws
+            .iter()
+            .enumerate()
+            .map(|(c, counts)| (c, gain(counts, bag[c])))
+            .fold(None, |best: Option<(usize, f64)>, (c, g)| match best {
+                Some((_, b)) if b >= g => best,
+                _ => Some((c, g)),
+            })
+        else {
+            break;
+        };
+        bag[c] += 1;
+        relative += color_gain - gain(&sizes, total);
+        if relative > best.0 {
+            best = (relative, bag.clone());
+        }
+    }
+
+    let at_limit = best.1.iter().sum::<i64>() == max_cubes as i64 && smallest < max_cubes as i64;
+    let bag = as_cubes(&best.1);
+    let log_likelihood = games.iter().map(|g| log_likelihood(&g.rounds, &bag)).sum();
+    Ok(Estimate {
+        bag,
+        log_likelihood,
+        at_limit,
+    })
+}
@@ -0,0 +1,107 @@
+//! Questions about a log under a given bag: which games it allows, where
+//! the others first go wrong, and the smallest bag allowing a set of games.
+
+use crate::{Cubes, Game};
+use aoc_core::{lines, read_input, GenResult};
+use std::path::Path;
+
+/// Parses a bag: `<count> <color>` entries separated by commas or on lines
+/// of their own, e.g. `12 red, 13 green, 14 blue`. Blank lines and `#`
+/// comments are ignored.
+pub fn parse_bag(text: &str) -> GenResult<Cubes> {
+    let mut bag = Cubes::new();
+
+    for line in lines(text) {
+        let content = line.text.split('#').next().unwrap_or("");
+        for entry in content.split(',').map(str::trim) {
+            if entry.is_empty() {
+                continue;
+            }
+            let (count_str, color) = line.split_pair(entry, ' ')?;
+            let count = line.parse::<i32>(count_str, "a cube count of at least 0")?;
+            if count < 0 {
+                return Err(line.error(count_str, "a cube count of at least 0"));
+            }
+            let color = color.trim();
+            if bag.insert(color.to_string(), count).is_some() {
+                return Err(line.error(color, "a color not already in the bag"));
+            }
+        }
+    }
+
+    Ok(bag)
+}
+
+pub fn load_bag(path: &Path) -> GenResult<Cubes> {
+    parse_bag(&read_input(path)?).map_err(|e| e.with_path(path))
+}
+
+/// The first round of a game that `bag` cannot supply.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Offense {
+    /// 0-based index of the round.
+    pub round: usize,
+    /// Each color drawn beyond the bag's count: the color, the number
+    /// drawn and the bag's count.
+    pub over: Vec<(String, i32, i32)>,
+}
+
+/// Where `rounds` first draw more of a color than `bag` holds, if they do.
+pub fn first_offense(rounds: &[Cubes], bag: &Cubes) -> Option<Offense> {
+    rounds.iter().enumerate().find_map(|(round, cubes)| {
+        let over: Vec<_> = cubes
+            .iter()
+            .filter_map(|(color, &drawn)| {
+                let limit = bag.get(color).copied().unwrap_or(0);
+                (drawn > limit).then(|| (color.clone(), drawn, limit))
+            })
+            .collect();
+        (!over.is_empty()).then_some(Offense { round, over })
+    })
+}
+
+/// Splits `games` into those `bag` allows and those it does not, with the
+/// round each of the latter first fails at.
+pub fn query<'a>(games: &'a [Game], bag: &Cubes) -> (Vec<&'a Game>, Vec<(&'a Game, Offense)>) {
+    let mut feasible = vec![];
+    let mut infeasible = vec![];
+    for game in games {
+        match first_offense(&game.rounds, bag) {
+            None => feasible.push(game),
+            Some(offense) => infeasible.push((game, offense)),
+        }
+    }
+
+    (feasible, infeasible)
+}
+
+/// The smallest bag allowing every one of `games`.
+pub fn smallest_bag<'a>(games: impl IntoIterator<Item = &'a Game>) -> Cubes {
+    let mut bag = Cubes::new();
+    for game in games {
+        for (color, n) in crate::min_bag(&game.rounds) {
+            let most = bag.entry(color).or_insert(0);
+            *most = (*most).max(n);
+        }
+    }
+
+    bag
+}
+
+/// Writes `cubes` as `parse_bag` reads them, `colors` first and in order
+/// (with 0 for those missing), then any others.
+pub fn describe(cubes: &Cubes, colors: &[String]) -> String {
+    let listed = colors
+        .iter()
+        .map(|color| (color, cubes.get(color).copied().unwrap_or(0)));
+    let others = cubes
+        .iter()
+        .filter(|(color, _)| !colors.contains(color))
+        .map(|(color, &n)| (color, n));
+
+    let entries: Vec<String> = listed
+        .chain(others)
+        .map(|(color, n)| format!("{} {}", n, color))
+        .collect();
+    entries.join(", ")
+}
@@ -0,0 +1,95 @@
+//! One row per game: what it drew and whether a bag allows it.
+
+use crate::{fits, min_bag, power, Cubes, Game};
+use aoc_core::{Error, GenResult};
+use std::str::FromStr;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Summary {
+    pub id: usize,
+    pub rounds: usize,
+    /// The most of each color drawn in one round.
+    pub most: Cubes,
+    pub power: i64,
+    pub feasible: bool,
+}
+
+/// Summarizes each game; the power is taken over `colors`.
+pub fn summarize(games: &[Game], bag: &Cubes, colors: &[String]) -> GenResult<Vec<Summary>> {
+    games
+        .iter()
+        .map(|game| {
+            let most = min_bag(&game.rounds);
+            Ok(Summary {
+                id: game.id,
+                rounds: game.rounds.len(),
+                power: power(&most, colors).map_err(|e| format!("Game {}: {}", game.id, e))?,
+                feasible: fits(&most, bag),
+                most,
+            })
+        })
+        .collect()
+}
+
+/// A column of the report to sort by.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Column {
+    Id,
+    Rounds,
+    /// The most of this color drawn in one round.
+    Most(String),
+    Power,
+    Feasible,
+}
+
+/// `id`, `rounds`, `power`, `feasible` or a color name.
+impl FromStr for Column {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        Ok(match s {
+            "id" => Column::Id,
+            "rounds" => Column::Rounds,
+            "power" => Column::Power,
+            "feasible" => Column::Feasible,
+            "" => return Err("Expected a column name".into()),
+            color => Column::Most(color.to_string()),
+        })
+    }
+}
+
+/// Sorts rows by `column`, keeping the log's order among equal rows.
+/// Sorting by a color not in `colors` is an error.
+pub fn sort_summaries(
+    rows: &mut [Summary],
+    column: &Column,
+    colors: &[String],
+    descending: bool,
+) -> GenResult<()> {
+    if let Column::Most(color) = column {
+        if !colors.contains(color) {
+            return Err(format!(
+                "Unknown column '{}', expected id, rounds, {}, power or feasible",
+                color,
+                colors.join(", ")
+            )
+            .into());
+        }
+    }
+
+    let key = |row: &Summary| -> i64 {
+        match column {
+            Column::Id => row.id as i64,
+            Column::Rounds => row.rounds as i64,
+            Column::Most(color) => row.most.get(color).copied().unwrap_or(0) as i64,
+            Column::Power => row.power,
+            Column::Feasible => row.feasible as i64,
+        }
+    };
+    if descending {
+        rows.sort_by_key(|row| std::cmp::Reverse(key(row)));
+    } else {
+        rows.sort_by_key(key);
+    }
+    Ok(())
+}
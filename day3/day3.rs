@@ -1,197 +1,129 @@
-use std::collections::HashSet;
-use std::error::Error;
-use std::fs::read_to_string;
-use std::iter::FromIterator;
-
-fn read_lines() -> Result<Vec<String>, Box<dyn Error>> {
-    Ok(read_to_string("input.txt")?
-        .lines()
-        .map(String::from)
-        .collect())
-}
+mod gears;
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let input = read_lines()?;
+pub use gears::{total_value, Aggregate, Count, Gear, GearRules};
 
-    part1(&input)?;
-    part2(&input)?;
+use aoc_core::{lines, GenResult, Grid, Line, Pos, Solution};
 
-    Ok(())
-}
+pub struct Day3;
 
-fn part1(input: &Vec<String>) -> Result<(), Box<dyn Error>> {
-    let (numbers, symbols) = parse_schematic(input)?;
+impl Solution for Day3 {
+    type Parsed = Schematic;
+    type Answer1 = i64;
+    type Answer2 = i64;
 
-    let result: i32 = numbers
-        .iter()
-        .map(|num| {
-            if is_part_number(num, &symbols) {
-                num.num
-            } else {
-                0
-            }
-        })
-        .sum();
+    fn parse(input: &str) -> GenResult<Schematic> {
+        parse_schematic(input)
+    }
 
-    println!("part 1: {}", result);
+    fn part1(schematic: &Schematic) -> GenResult<i64> {
+        schematic
+            .numbers
+            .iter()
+            .filter(|num| is_part_number(num, schematic))
+            .try_fold(0i64, |sum, num| sum.checked_add(num.num as i64))
+            .ok_or_else(|| "The part numbers add up to more than an i64 holds".into())
+    }
 
-    Ok(())
+    fn part2(schematic: &Schematic) -> GenResult<i64> {
+        total_value(&schematic.gears(&GearRules::default())?)
+    }
 }
 
-fn part2(input: &Vec<String>) -> Result<(), Box<dyn Error>> {
-    let (numbers, symbols) = parse_schematic(input)?;
-
-    let result: i32 = symbols
-        .iter()
-        .map(|symb| gear_ratio(symb, &numbers))
-        .flatten()
-        .sum();
-
-    println!("part 2: {}", result);
+pub struct Schematic {
+    numbers: Vec<Number>,
+    symbols: Vec<Symbol>,
+    /// What occupies each cell.
+    cells: Grid<Cell>,
+}
 
-    Ok(())
+/// An index into `Schematic::numbers` or `Schematic::symbols`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Cell {
+    Empty,
+    Number(usize),
+    Symbol(usize),
 }
 
 #[derive(Debug)]
 struct Number {
     num: i32,
-    row: i32,
-    start_col: i32,
-    end_col: i32,
+    /// The leftmost digit.
+    start: Pos,
+    len: usize,
 }
 
 #[derive(Debug)]
 struct Symbol {
-    symb: String,
-    row: i32,
-    col: i32,
+    symb: char,
+    pos: Pos,
 }
 
-fn parse_schematic(
-    schematic_raw: &Vec<String>,
-) -> Result<(Vec<Number>, Vec<Symbol>), Box<dyn Error>> {
+fn parse_schematic(schematic_raw: &str) -> GenResult<Schematic> {
+    // Short lines are padded with empty cells rather than rejected.
+    let chars = Grid::parse_ragged(schematic_raw, "any character", '.', Some)?;
+    let mut cells = Grid::new(chars.width(), chars.height(), Cell::Empty);
     let mut numbers = vec![];
     let mut symbols = vec![];
 
-    for (row, line) in schematic_raw.iter().enumerate() {
-        let mut row_numbers = parse_numbers(row as i32, line)?;
-        let mut row_symbols = parse_symbols(row as i32, line)?;
-
-        numbers.append(&mut row_numbers);
-        symbols.append(&mut row_symbols);
-    }
-
-    Ok((numbers, symbols))
-}
-
-fn parse_numbers(row: i32, line: &str) -> Result<Vec<Number>, Box<dyn Error>> {
-    let mut numbers = vec![];
-
-    let num_chars = line.match_indices(char::is_numeric).collect::<Vec<_>>();
-
-    let mut ind_splits = vec![];
-    for window in num_chars.windows(2) {
-        let (prev_ind, _) = window[0];
-        let (next_ind, _) = window[1];
+    for (line, row) in lines(schematic_raw).zip(chars.rows()) {
+        let pos = |col| Pos::new(line.number - 1, col);
+        let mut col = 0;
+        while col < row.len() {
+            if !row[col].is_numeric() {
+                if row[col] != '.' {
+                    cells[pos(col)] = Cell::Symbol(symbols.len());
+                    symbols.push(Symbol {
+                        symb: row[col],
+                        pos: pos(col),
+                    });
+                }
+                col += 1;
+                continue;
+            }
 
-        if prev_ind + 1 != next_ind {
-            ind_splits.push(prev_ind);
+            let start = col;
+            while col < row.len() && row[col].is_numeric() {
+                cells[pos(col)] = Cell::Number(numbers.len());
+                col += 1;
+            }
+            let num = parse_number(&line, &row[start..col], start)?;
+            numbers.push(Number {
+                num,
+                start: pos(start),
+                len: col - start,
+            });
         }
     }
 
-    let ind_splits_set: HashSet<usize> = HashSet::from_iter(ind_splits);
-    for ind_digit_pairs in num_chars.split_inclusive(|(i, _)| ind_splits_set.contains(i)) {
-        let start_col = ind_digit_pairs
-            .iter()
-            .map(|(i, _)| i)
-            .cloned()
-            .min()
-            .ok_or(format!(
-                "Could not find min of number in {:?}",
-                ind_digit_pairs
-            ))?;
-
-        let end_col = ind_digit_pairs
-            .iter()
-            .map(|(i, _)| i)
-            .cloned()
-            .max()
-            .ok_or(format!(
-                "Could not find max of number in {:?}",
-                ind_digit_pairs
-            ))?;
-
-        let num = ind_digit_pairs
-            .iter()
-            .map(|(_, c)| c)
-            .cloned()
-            .collect::<String>()
-            .parse::<i32>()?;
-
-        numbers.push(Number {
-            num: num,
-            row: row,
-            start_col: start_col as i32,
-            end_col: end_col as i32,
-        });
-    }
-
-    Ok(numbers)
-}
-
-fn parse_symbols(row: i32, line: &str) -> Result<Vec<Symbol>, Box<dyn Error>> {
-    let mut symbols = vec![];
-
-    let symbol_chars = line
-        .match_indices(|c| !char::is_numeric(c) && c != '.')
-        .collect::<Vec<_>>();
-
-    for (i, s) in symbol_chars {
-        symbols.push(Symbol {
-            symb: s.to_string(),
-            row: row,
-            col: i as i32,
-        });
-    }
-
-    Ok(symbols)
+    Ok(Schematic {
+        numbers,
+        symbols,
+        cells,
+    })
 }
 
-fn is_part_number(number: &Number, symbols: &Vec<Symbol>) -> bool {
-    symbols.iter().any(|symbol| are_adjacent(number, symbol))
-}
-
-fn gear_ratio(symbol: &Symbol, numbers: &Vec<Number>) -> Option<i32> {
-    let adjacent_numbers = numbers
+/// Parses the digits found from column `start` of `line`.
+fn parse_number(line: &Line, digits: &[char], start: usize) -> GenResult<i32> {
+    digits
         .iter()
-        .filter(|number| are_adjacent(number, symbol))
-        .collect::<Vec<&Number>>();
-
-    if symbol.symb == "*" && adjacent_numbers.len() == 2 {
-        Some(
-            adjacent_numbers
-                .iter()
-                .map(|n| n.num)
-                .fold(1, |acc, n| acc * n),
-        )
-    } else {
-        None
-    }
+        .collect::<String>()
+        .parse::<i32>()
+        .map_err(|_| {
+            // Columns count characters; the diagnostic wants bytes.
+            let mut bytes = line.text.char_indices().map(|(i, _)| i).skip(start);
+            let from = bytes.next().unwrap_or(line.text.len());
+            let to = bytes.nth(digits.len() - 1).unwrap_or(line.text.len());
+            line.error_at(from..to, "a number that fits in 32 bits")
+        })
 }
 
-fn are_adjacent(number: &Number, symbol: &Symbol) -> bool {
-    let row_low = number.row - 1;
-    let row_high = number.row + 1;
-    let col_low = number.start_col - 1;
-    let col_high = number.end_col + 1;
-
-    for row in row_low..=row_high {
-        for col in col_low..=col_high {
-            if symbol.row == row && symbol.col == col {
-                return true;
-            }
-        }
-    }
-
-    return false;
+fn is_part_number(number: &Number, schematic: &Schematic) -> bool {
+    let Pos { row, col } = number.start;
+    schematic
+        .cells
+        .region(
+            row.saturating_sub(1)..row + 2,
+            col.saturating_sub(1)..col + number.len + 1,
+        )
+        .any(|(_, cell)| matches!(cell, Cell::Symbol(_)))
 }
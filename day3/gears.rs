@@ -0,0 +1,170 @@
+//! Which symbols count as gears and what a gear is worth.
+
+use crate::{Cell, Schematic};
+use aoc_core::{Error, GenResult, Pos};
+use std::str::FromStr;
+
+/// How many numbers a gear must touch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Count {
+    Exactly(usize),
+    AtLeast(usize),
+    AtMost(usize),
+}
+
+impl Count {
+    pub fn allows(self, n: usize) -> bool {
+        match self {
+            Count::Exactly(k) => n == k,
+            Count::AtLeast(k) => n >= k,
+            Count::AtMost(k) => n <= k,
+        }
+    }
+}
+
+/// `N`, `>=N` or `<=N`.
+impl FromStr for Count {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let (count, n): (fn(usize) -> Count, &str) = if let Some(n) = s.strip_prefix(">=") {
+            (Count::AtLeast, n)
+        } else if let Some(n) = s.strip_prefix("<=") {
+            (Count::AtMost, n)
+        } else {
+            (Count::Exactly, s)
+        };
+        let n = n
+            .trim()
+            .parse()
+            .map_err(|_| format!("Expected a count like 2, >=2 or <=2, found '{}'", s))?;
+        Ok(count(n))
+    }
+}
+
+/// How a gear's numbers combine into its value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Aggregate {
+    Product,
+    Sum,
+    Max,
+}
+
+impl Aggregate {
+    /// The combined value, or `None` if it overflows an i64.
+    fn apply(self, numbers: &[i32]) -> Option<i64> {
+        let mut numbers = numbers.iter().map(|&n| n as i64);
+        match self {
+            Aggregate::Product => numbers.try_fold(1i64, i64::checked_mul),
+            Aggregate::Sum => numbers.try_fold(0i64, i64::checked_add),
+            Aggregate::Max => Some(numbers.max().unwrap_or(0)),
+        }
+    }
+}
+
+impl FromStr for Aggregate {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        match s {
+            "product" => Ok(Aggregate::Product),
+            "sum" => Ok(Aggregate::Sum),
+            "max" => Ok(Aggregate::Max),
+            _ => Err(format!("Unknown aggregate '{}', expected product, sum or max", s).into()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GearRules {
+    /// The symbols that may be gears; `None` for any symbol.
+    pub symbols: Option<Vec<char>>,
+    pub count: Count,
+    pub aggregate: Aggregate,
+}
+
+/// The puzzle's gears: a `*` touching exactly two numbers, worth their
+/// product.
+impl Default for GearRules {
+    fn default() -> Self {
+        GearRules {
+            symbols: Some(vec!['*']),
+            count: Count::Exactly(2),
+            aggregate: Aggregate::Product,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Gear {
+    pub symbol: char,
+    pub pos: Pos,
+    /// The numbers touching the symbol, in reading order.
+    pub numbers: Vec<i32>,
+    pub value: i64,
+}
+
+/// The sum of the gears' values, or an error if it overflows an i64.
+pub fn total_value(gears: &[Gear]) -> GenResult<i64> {
+    gears
+        .iter()
+        .try_fold(0i64, |total, gear| total.checked_add(gear.value))
+        .ok_or_else(|| "The gears' values add up to more than an i64 holds".into())
+}
+
+impl Schematic {
+    /// The symbols that are gears under `rules`, in reading order. A symbol
+    /// touching no number is never a gear; one whose value overflows an i64
+    /// is an error.
+    pub fn gears(&self, rules: &GearRules) -> GenResult<Vec<Gear>> {
+        self.symbols
+            .iter()
+            .filter(|symbol| {
+                rules
+                    .symbols
+                    .as_ref()
+                    .is_none_or(|allowed| allowed.contains(&symbol.symb))
+            })
+            .filter_map(|symbol| {
+                let numbers: Vec<i32> = self
+                    .adjacent_numbers(symbol.pos)
+                    .into_iter()
+                    .map(|i| self.numbers[i].num)
+                    .collect();
+                if numbers.is_empty() || !rules.count.allows(numbers.len()) {
+                    return None;
+                }
+                let Some(value) = rules.aggregate.apply(&numbers) else {
+                    return Some(Err(format!(
+                        "The gear at {}:{} is worth more than an i64 holds",
+                        symbol.pos.row + 1,
+                        symbol.pos.col + 1
+                    )
+                    .into()));
+                };
+                Some(Ok(Gear {
+                    symbol: symbol.symb,
+                    pos: symbol.pos,
+                    value,
+                    numbers,
+                }))
+            })
+            .collect()
+    }
+
+    /// Indices of the numbers touching `pos`. A number touching it at
+    /// several digits is listed once.
+    fn adjacent_numbers(&self, pos: Pos) -> Vec<usize> {
+        let mut adjacent = self
+            .cells
+            .neighbors8(pos)
+            .filter_map(|pos| match self.cells[pos] {
+                Cell::Number(i) => Some(i),
+                _ => None,
+            })
+            .collect::<Vec<usize>>();
+        adjacent.sort_unstable();
+        adjacent.dedup();
+        adjacent
+    }
+}
@@ -1,87 +1,78 @@
-use std::error::Error;
-use std::fs::read_to_string;
+use aoc_core::{lines, GenResult, Line, Solution};
 
-type GenResult<T> = Result<T, Box<dyn Error>>;
+pub struct Day5;
 
-fn read_lines() -> GenResult<Vec<String>> {
-    Ok(read_to_string("input.txt")?
-        .lines()
-        .map(String::from)
-        .collect())
-}
+impl Solution for Day5 {
+    type Parsed = Almanac;
+    type Answer1 = i64;
+    type Answer2 = i64;
 
-fn main() -> GenResult<()> {
-    let input = read_lines()?;
+    fn parse(input: &str) -> GenResult<Almanac> {
+        let lines: Vec<Line> = lines(input).collect();
+        let chunks: Vec<&[Line]> = lines.split(|l| l.text.is_empty()).collect();
 
-    part1(&input)?;
-    part2(&input)?;
+        let (raw_seeds, raw_maps) = chunks.split_first().ok_or("Expected non-empty input")?;
 
-    Ok(())
-}
+        let seeds = parse_seeds(raw_seeds.first().ok_or("Expected a seeds line")?)?;
+        let maps = raw_maps
+            .iter()
+            .map(|chunk| parse_map(chunk))
+            .collect::<Result<Vec<_>, _>>()?;
 
-fn part1(input: &Vec<String>) -> GenResult<()> {
-    let chunks: Vec<&[String]> = input.split(|s| s.is_empty()).collect();
+        Ok(Almanac { seeds, maps })
+    }
 
-    let (raw_seeds, raw_maps) = chunks.split_first().ok_or("Expected non-empty input")?;
+    fn part1(almanac: &Almanac) -> GenResult<i64> {
+        let maps: Vec<Map> = almanac
+            .maps
+            .iter()
+            .map(|entries| {
+                let mut entries_by_source = entries.to_vec();
+                entries_by_source.sort_by_key(|entry| entry.source);
+                Map { entries_by_source }
+            })
+            .collect();
+
+        let mut values = almanac.seeds.to_vec();
+        for map in maps {
+            let new_values = values
+                .iter()
+                .map(|value| apply_map(*value, &map))
+                .collect::<Result<Vec<_>, _>>()?;
+            values = new_values;
+        }
 
-    let seeds = parse_seeds(&raw_seeds[0])?;
-    let mut raw_entries: Vec<Vec<Entry>> = raw_maps
-        .iter()
-        .map(|chunk| parse_map(&chunk.to_vec()))
-        .collect::<Result<Vec<_>, _>>()?;
+        let result = values
+            .iter()
+            .min()
+            .ok_or("Expected values to be nonempty")?;
 
-    for entries in &mut raw_entries {
-        entries.sort_by_key(|entry| entry.source);
+        Ok(*result)
     }
 
-    let maps: Vec<Map> = raw_entries
-        .iter()
-        .map(|entries| Map {
-            entries_by_source: entries.to_vec(),
-        })
-        .collect();
-
-    let mut values = seeds;
-    for map in maps {
-        let new_values = values
+    fn part2(almanac: &Almanac) -> GenResult<i64> {
+        let seeds = seed_ranges(&almanac.seeds)?;
+        let transforms: Vec<Vec<Transform>> = almanac
+            .maps
             .iter()
-            .map(|value| apply_map(*value, &map))
-            .collect::<Result<Vec<_>, _>>()?;
-        values = new_values;
-    }
+            .map(|entries| entries.iter().map(Transform::from).collect())
+            .collect();
 
-    let result = values
-        .iter()
-        .min()
-        .ok_or("Expected values to be nonempty")?;
+        let final_seeds = apply_maps(&seeds, &transforms);
 
-    println!("part 1: {}", result);
+        let result = final_seeds
+            .iter()
+            .map(|r| r.start)
+            .min()
+            .ok_or("Expected final_seeds to be nonempty")?;
 
-    Ok(())
+        Ok(result)
+    }
 }
 
-fn part2(input: &Vec<String>) -> GenResult<()> {
-    let chunks: Vec<&[String]> = input.split(|s| s.is_empty()).collect();
-
-    let (raw_seeds, raw_maps) = chunks.split_first().ok_or("Expected non-empty input")?;
-
-    let seeds = parse_seed_ranges(&raw_seeds[0])?;
-    let transforms: Vec<Vec<Transform>> = raw_maps
-        .iter()
-        .map(|chunk| parse_transforms(&chunk.to_vec()))
-        .collect::<Result<Vec<_>, _>>()?;
-
-    let final_seeds = apply_maps(&seeds, &transforms);
-
-    let result = final_seeds
-        .iter()
-        .map(|r| r.start)
-        .min()
-        .ok_or("Expected final_seeds to be nonempty")?;
-
-    println!("part 2: {}", result);
-
-    Ok(())
+pub struct Almanac {
+    seeds: Vec<i64>,
+    maps: Vec<Vec<Entry>>,
 }
 
 #[derive(Debug)]
@@ -109,25 +100,27 @@ struct Transform {
     amount: i64,
 }
 
-fn parse_seeds(line: &String) -> GenResult<Vec<i64>> {
-    let (_, nums) = line
-        .split_once(':')
-        .ok_or(format!("Could not find ':' in line: {}", line))?;
-
-    Ok(nums
-        .trim()
-        .split_whitespace()
-        .map(|num| num.parse::<i64>())
-        .collect::<Result<Vec<_>, _>>()?)
+impl From<&Entry> for Transform {
+    fn from(entry: &Entry) -> Self {
+        Transform {
+            start: entry.source,
+            end: entry.source + entry.len - 1,
+            amount: entry.target - entry.source,
+        }
+    }
 }
 
-fn parse_seed_ranges(line: &String) -> GenResult<Vec<Range>> {
-    let parsed_nums = parse_seeds(line)?;
+fn parse_seeds(line: &Line) -> GenResult<Vec<i64>> {
+    let (_, nums) = line.split_pair(line.text, ':')?;
+
+    line.parse_nums(nums)
+}
 
-    Ok(parsed_nums
+fn seed_ranges(seeds: &[i64]) -> GenResult<Vec<Range>> {
+    Ok(seeds
         .chunks(2)
         .map(|c| {
-            c.get(0)
+            c.first()
                 .zip(c.get(1))
                 .ok_or(format!("Chunk was not size 2: {:?}", c))
                 .map(|(s, e)| Range {
@@ -138,21 +131,18 @@ fn parse_seed_ranges(line: &String) -> GenResult<Vec<Range>> {
         .collect::<Result<Vec<_>, _>>()?)
 }
 
-fn parse_map(raw_map: &Vec<String>) -> GenResult<Vec<Entry>> {
+fn parse_map(raw_map: &[Line]) -> GenResult<Vec<Entry>> {
     let (_, entries) = raw_map
         .split_first()
         .ok_or("Expected non-empty vec in raw_map")?;
 
     let mut parsed: Vec<Entry> = vec![];
     for entry in entries.iter() {
-        let nums = entry
-            .split_whitespace()
-            .map(|s| s.parse::<i64>())
-            .collect::<Result<Vec<_>, _>>()?;
+        let nums = entry.parse_nums::<i64>(entry.text)?;
 
-        let entry_fields = match (nums.get(0), nums.get(1), nums.get(2)) {
+        let entry_fields = match (nums.first(), nums.get(1), nums.get(2)) {
             (Some(target), Some(source), Some(len)) => Ok((target, source, len)),
-            _ => Err(format!("Entry did not contain 3 elements: {}", entry)),
+            _ => Err(entry.error(entry.text, "3 numbers: <destination> <source> <length>")),
         }?;
 
         parsed.push(Entry {
@@ -165,38 +155,6 @@ fn parse_map(raw_map: &Vec<String>) -> GenResult<Vec<Entry>> {
     Ok(parsed)
 }
 
-fn parse_transforms(raw_map: &Vec<String>) -> GenResult<Vec<Transform>> {
-    let (_, entries) = raw_map
-        .split_first()
-        .ok_or("Expected non-empty vec in raw_map")?;
-
-    let mut parsed: Vec<Transform> = vec![];
-    for entry in entries.iter() {
-        let nums = entry
-            .split_whitespace()
-            .map(|s| s.parse::<i64>())
-            .collect::<Result<Vec<_>, _>>()?;
-
-        let entry_fields = match (nums.get(0), nums.get(1), nums.get(2)) {
-            (Some(target), Some(source), Some(len)) => {
-                let start = *source;
-                let end = source + len - 1;
-                let amount = target - source;
-                Ok((start, end, amount))
-            }
-            _ => Err(format!("Entry did not contain 3 elements: {}", entry)),
-        }?;
-
-        parsed.push(Transform {
-            start: entry_fields.0,
-            end: entry_fields.1,
-            amount: entry_fields.2,
-        });
-    }
-
-    Ok(parsed)
-}
-
 fn apply_map(input: i64, map: &Map) -> GenResult<i64> {
     let partition_index = map
         .entries_by_source
@@ -221,21 +179,20 @@ fn apply_map(input: i64, map: &Map) -> GenResult<i64> {
     }
 }
 
-fn apply_maps(seeds: &Vec<Range>, maps: &Vec<Vec<Transform>>) -> Vec<Range> {
+fn apply_maps(seeds: &[Range], maps: &[Vec<Transform>]) -> Vec<Range> {
     maps.iter().fold(seeds.to_vec(), |ranges, transforms| {
         process_ranges(&ranges, transforms)
     })
 }
 
-fn process_ranges(ranges: &Vec<Range>, transforms: &Vec<Transform>) -> Vec<Range> {
+fn process_ranges(ranges: &[Range], transforms: &[Transform]) -> Vec<Range> {
     ranges
         .iter()
-        .map(|range| apply_transforms(range, transforms))
-        .flatten()
+        .flat_map(|range| apply_transforms(range, transforms))
         .collect()
 }
 
-fn apply_transforms(range: &Range, transforms: &Vec<Transform>) -> Vec<Range> {
+fn apply_transforms(range: &Range, transforms: &[Transform]) -> Vec<Range> {
     let new_ranges: Vec<(Range, i64)> = transforms
         .iter()
         .filter_map(|t| apply_transform(range, t))
@@ -254,9 +211,9 @@ fn apply_transforms(range: &Range, transforms: &Vec<Transform>) -> Vec<Range> {
     original_splits
 }
 
-fn split_original_range(range: &Range, affected_ranges: &Vec<(Range, i64)>) -> Vec<Range> {
+fn split_original_range(range: &Range, affected_ranges: &[(Range, i64)]) -> Vec<Range> {
     if affected_ranges.is_empty() {
-        vec![range.clone()]
+        vec![*range]
     } else {
         let dummy_first_range = Range {
             start: range.start - 1,
@@ -293,15 +250,7 @@ fn split_original_range(range: &Range, affected_ranges: &Vec<(Range, i64)>) -> V
 fn apply_transform(range: &Range, transform: &Transform) -> Option<(Range, i64)> {
     let overlap = find_overlap((range.start, range.end), (transform.start, transform.end));
 
-    overlap.map(|(start, end)| {
-        (
-            Range {
-                start: start,
-                end: end,
-            },
-            transform.amount,
-        )
-    })
+    overlap.map(|(start, end)| (Range { start, end }, transform.amount))
 }
 
 fn find_overlap(left: (i64, i64), right: (i64, i64)) -> Option<(i64, i64)> {
@@ -1,41 +1,36 @@
-use std::error::Error;
-use std::fs::read_to_string;
+use aoc_core::{lines, GenResult, Line, Solution};
 use std::iter::zip;
 
-type GenResult<T> = Result<T, Box<dyn Error>>;
+pub struct Day6;
 
-fn read_lines() -> GenResult<Vec<String>> {
-    Ok(read_to_string("input.txt")?
-        .lines()
-        .map(String::from)
-        .collect())
-}
-
-fn main() -> GenResult<()> {
-    let input = read_lines()?;
+impl Solution for Day6 {
+    type Parsed = RaceSheet;
+    type Answer1 = i64;
+    type Answer2 = i64;
 
-    part1(&input)?;
-    part2(&input)?;
+    fn parse(input: &str) -> GenResult<RaceSheet> {
+        let lines: Vec<Line> = lines(input).collect();
 
-    Ok(())
-}
-
-fn part1(input: &Vec<String>) -> GenResult<()> {
-    let races = parse_races(input)?;
-    let result: i64 = races.iter().map(compute_margin_count).product();
+        Ok(RaceSheet {
+            races: parse_races(&lines)?,
+            race: parse_race(&lines)?,
+        })
+    }
 
-    println!("part 1: {}", result);
+    fn part1(sheet: &RaceSheet) -> GenResult<i64> {
+        Ok(sheet.races.iter().map(compute_margin_count).product())
+    }
 
-    Ok(())
+    fn part2(sheet: &RaceSheet) -> GenResult<i64> {
+        Ok(compute_margin_count(&sheet.race))
+    }
 }
 
-fn part2(input: &Vec<String>) -> GenResult<()> {
-    let race = parse_race(input)?;
-    let result = compute_margin_count(&race);
-
-    println!("part 2: {}", result);
-
-    Ok(())
+/// The races read column by column, and the single race read with the
+/// whitespace between digits removed.
+pub struct RaceSheet {
+    races: Vec<Race>,
+    race: Race,
 }
 
 struct Race {
@@ -59,10 +54,13 @@ fn compute_margin(time: f64, distance: f64, is_addition: bool) -> f64 {
     top / 2.0
 }
 
-fn parse_race(input: &Vec<String>) -> GenResult<Race> {
-    let parsed_lines = input.iter().map(parse_num).collect::<Result<Vec<_>, _>>()?;
+fn parse_race(input: &[Line]) -> GenResult<Race> {
+    let parsed_lines = input
+        .iter()
+        .map(|s| parse_num(s))
+        .collect::<Result<Vec<_>, _>>()?;
 
-    let time = parsed_lines.get(0).ok_or(format!(
+    let time = parsed_lines.first().ok_or(format!(
         "Expected parsed_lines size to equal 2: {:?}",
         parsed_lines
     ))?;
@@ -77,25 +75,24 @@ fn parse_race(input: &Vec<String>) -> GenResult<Race> {
     })
 }
 
-fn parse_num(line: &String) -> GenResult<i64> {
-    let (_, raw_untrimmed_nums) = line
-        .split_once(':')
-        .ok_or(format!("Expected ':' in line: {}", line))?;
+fn parse_num(line: &Line) -> GenResult<i64> {
+    let (_, raw_untrimmed_nums) = line.split_pair(line.text, ':')?;
 
-    Ok(raw_untrimmed_nums
+    raw_untrimmed_nums
         .chars()
         .filter(|c| !c.is_whitespace())
         .collect::<String>()
-        .parse::<i64>()?)
+        .parse::<i64>()
+        .map_err(|_| line.error(raw_untrimmed_nums, "digits separated by whitespace"))
 }
 
-fn parse_races(input: &Vec<String>) -> GenResult<Vec<Race>> {
+fn parse_races(input: &[Line]) -> GenResult<Vec<Race>> {
     let parsed_lines = input
         .iter()
-        .map(parse_nums)
+        .map(|s| parse_nums(s))
         .collect::<Result<Vec<_>, _>>()?;
 
-    let time = parsed_lines.get(0).ok_or(format!(
+    let time = parsed_lines.first().ok_or(format!(
         "Expected parsed_lines size to equal 2: {:?}",
         parsed_lines
     ))?;
@@ -112,14 +109,8 @@ fn parse_races(input: &Vec<String>) -> GenResult<Vec<Race>> {
         .collect())
 }
 
-fn parse_nums(line: &String) -> GenResult<Vec<i64>> {
-    let (_, raw_untrimmed_nums) = line
-        .split_once(':')
-        .ok_or(format!("Expected ':' in line: {}", line))?;
+fn parse_nums(line: &Line) -> GenResult<Vec<i64>> {
+    let (_, raw_untrimmed_nums) = line.split_pair(line.text, ':')?;
 
-    Ok(raw_untrimmed_nums
-        .trim()
-        .split_whitespace()
-        .map(|num| num.parse::<i64>())
-        .collect::<Result<Vec<_>, _>>()?)
+    line.parse_nums(raw_untrimmed_nums)
 }
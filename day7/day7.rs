@@ -1,83 +1,68 @@
+use aoc_core::{lines, GenResult, Line, Solution};
 use std::cmp::Ordering;
 use std::collections::HashMap;
-use std::error::Error;
-use std::fs::read_to_string;
 use std::iter::zip;
 
-type GenResult<T> = Result<T, Box<dyn Error>>;
+pub struct Day7;
 
-fn read_lines() -> GenResult<Vec<String>> {
-    Ok(read_to_string("input.txt")?
-        .lines()
-        .map(String::from)
-        .collect())
-}
-
-fn main() -> GenResult<()> {
-    let input = read_lines()?;
-
-    part1(&input)?;
-    part2(&input)?;
-
-    Ok(())
-}
-
-fn part1(input: &Vec<String>) -> GenResult<()> {
-    let card_map = HashMap::from([
-        ('A', 13),
-        ('K', 12),
-        ('Q', 11),
-        ('J', 10),
-        ('T', 9),
-        ('9', 8),
-        ('8', 7),
-        ('7', 6),
-        ('6', 5),
-        ('5', 4),
-        ('4', 3),
-        ('3', 2),
-        ('2', 1),
-    ]);
-
-    let mut hands = input
-        .iter()
-        .map(|line| parse_hand(line, &card_map, false))
-        .collect::<Result<Vec<_>, _>>()?;
-
-    let result = score_hands(&mut hands);
+impl Solution for Day7 {
+    type Parsed = Vec<RawHand>;
+    type Answer1 = i32;
+    type Answer2 = i32;
 
-    println!("part 1: {}", result);
-
-    Ok(())
-}
-
-fn part2(input: &Vec<String>) -> GenResult<()> {
-    let card_map = HashMap::from([
-        ('A', 13),
-        ('K', 12),
-        ('Q', 11),
-        ('T', 10),
-        ('9', 9),
-        ('8', 8),
-        ('7', 7),
-        ('6', 6),
-        ('5', 5),
-        ('4', 4),
-        ('3', 3),
-        ('2', 2),
-        ('J', 1),
-    ]);
-
-    let mut hands = input
-        .iter()
-        .map(|line| parse_hand(line, &card_map, true))
-        .collect::<Result<Vec<_>, _>>()?;
-
-    let result = score_hands(&mut hands);
+    fn parse(input: &str) -> GenResult<Vec<RawHand>> {
+        lines(input).map(|line| parse_hand(&line)).collect()
+    }
 
-    println!("part 2: {}", result);
+    fn part1(hands: &Vec<RawHand>) -> GenResult<i32> {
+        let card_map = HashMap::from([
+            ('A', 13),
+            ('K', 12),
+            ('Q', 11),
+            ('J', 10),
+            ('T', 9),
+            ('9', 8),
+            ('8', 7),
+            ('7', 6),
+            ('6', 5),
+            ('5', 4),
+            ('4', 3),
+            ('3', 2),
+            ('2', 1),
+        ]);
+
+        let mut hands = hands
+            .iter()
+            .map(|raw| build_hand(raw, &card_map, false))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(score_hands(&mut hands))
+    }
 
-    Ok(())
+    fn part2(hands: &Vec<RawHand>) -> GenResult<i32> {
+        let card_map = HashMap::from([
+            ('A', 13),
+            ('K', 12),
+            ('Q', 11),
+            ('T', 10),
+            ('9', 9),
+            ('8', 8),
+            ('7', 7),
+            ('6', 6),
+            ('5', 5),
+            ('4', 4),
+            ('3', 3),
+            ('2', 2),
+            ('J', 1),
+        ]);
+
+        let mut hands = hands
+            .iter()
+            .map(|raw| build_hand(raw, &card_map, true))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(score_hands(&mut hands))
+    }
 }
 
 #[derive(Debug)]
@@ -87,7 +72,7 @@ struct Hand {
     bid: i32,
 }
 
-fn score_hands(hands: &mut Vec<Hand>) -> i32 {
+fn score_hands(hands: &mut [Hand]) -> i32 {
     hands.sort_by(|l, r| {
         let res = l.hand_type.cmp(&r.hand_type);
         if res == Ordering::Equal {
@@ -104,21 +89,40 @@ fn score_hands(hands: &mut Vec<Hand>) -> i32 {
         .sum()
 }
 
-fn parse_hand(line: &String, card_map: &HashMap<char, i32>, jokers: bool) -> GenResult<Hand> {
-    let (cards, bid_str) = line
-        .split_once(' ')
-        .ok_or(format!("Invalid line: {}", line))?;
+/// A hand as written in the input, before card values are assigned.
+pub struct RawHand {
+    cards: String,
+    bid: i32,
+}
+
+fn parse_hand(line: &Line) -> GenResult<RawHand> {
+    let (cards, bid_str) = line.split_pair(line.text, ' ')?;
+
+    if let Some((i, c)) = cards
+        .char_indices()
+        .find(|(_, c)| !"AKQJT98765432".contains(*c))
+    {
+        let start = line.span_of(cards).start + i;
+        return Err(line.error_at(start..start + c.len_utf8(), "a card (one of AKQJT98765432)"));
+    }
+
+    Ok(RawHand {
+        cards: cards.to_string(),
+        bid: line.parse(bid_str, "a bid")?,
+    })
+}
 
+fn build_hand(raw: &RawHand, card_map: &HashMap<char, i32>, jokers: bool) -> GenResult<Hand> {
+    let cards = raw.cards.as_str();
     let card_values = cards
         .chars()
         .map(|c| {
             card_map
                 .get(&c)
-                .map(|n| *n)
+                .copied()
                 .ok_or(format!("Got invalid card {}", c))
         })
         .collect::<Result<Vec<_>, _>>()?;
-    let bid = bid_str.parse::<i32>()?;
     let hand_type = if jokers {
         classify_hand_jokers(cards)
     } else {
@@ -126,9 +130,9 @@ fn parse_hand(line: &String, card_map: &HashMap<char, i32>, jokers: bool) -> Gen
     };
 
     Ok(Hand {
-        card_values: card_values,
-        hand_type: hand_type,
-        bid: bid,
+        card_values,
+        hand_type,
+        bid: raw.bid,
     })
 }
 
@@ -200,7 +204,7 @@ fn classify_hand_jokers(hand: &str) -> i32 {
     }
 }
 
-fn break_tie(left: &Vec<i32>, right: &Vec<i32>) -> Ordering {
+fn break_tie(left: &[i32], right: &[i32]) -> Ordering {
     for (l, r) in zip(left, right) {
         if l < r {
             return Ordering::Less;
@@ -1,77 +1,60 @@
+use aoc_core::{lines, GenResult, Line, Solution};
 use std::collections::HashMap;
-use std::error::Error;
-use std::fs::read_to_string;
 
-type GenResult<T> = Result<T, Box<dyn Error>>;
+pub struct Day8;
 
-fn read_lines() -> GenResult<Vec<String>> {
-    Ok(read_to_string("input.txt")?
-        .lines()
-        .map(String::from)
-        .collect())
-}
-
-fn main() -> GenResult<()> {
-    let input = read_lines()?;
-
-    part1(&input)?;
-    part2(&input)?;
-
-    Ok(())
-}
-
-fn part1(input: &Vec<String>) -> GenResult<()> {
-    let (instructions, network) = parse_input(input)?;
-    let result = traverse_network(&instructions, &network, "AAA", |s| s == "ZZZ")?;
+impl Solution for Day8 {
+    type Parsed = (Vec<char>, HashMap<String, Node>);
+    type Answer1 = i64;
+    type Answer2 = i64;
 
-    println!("part 1: {}", result);
-
-    Ok(())
-}
-
-fn part2(input: &Vec<String>) -> GenResult<()> {
-    let (instructions, network) = parse_input(input)?;
-    let result = traverse_all_paths(&instructions, &network)?;
+    fn parse(input: &str) -> GenResult<Self::Parsed> {
+        let lines: Vec<Line> = lines(input).collect();
+        parse_input(&lines)
+    }
 
-    println!("part 2: {}", result);
+    fn part1((instructions, network): &Self::Parsed) -> GenResult<i64> {
+        traverse_network(instructions, network, "AAA", |s| s == "ZZZ")
+    }
 
-    Ok(())
+    fn part2((instructions, network): &Self::Parsed) -> GenResult<i64> {
+        traverse_all_paths(instructions, network)
+    }
 }
 
-struct Node {
+pub struct Node {
     left: String,
     right: String,
 }
 
-fn parse_input(input: &Vec<String>) -> GenResult<(Vec<char>, HashMap<String, Node>)> {
+fn parse_input(input: &[Line]) -> GenResult<(Vec<char>, HashMap<String, Node>)> {
     let (instructions_raw, nodes_raw) = input
         .split_first()
-        .ok_or(format!("Invalid input, {:?}", input))?;
+        .ok_or("Expected a line of instructions")?;
 
     let nodes = nodes_raw
         .split_first()
-        .ok_or(format!("Invalid input, {:?}", input))?
+        .ok_or("Expected a blank line followed by the network")?
         .1
         .iter()
-        .map(|s| parse_node(s))
+        .map(parse_node)
         .collect::<Result<HashMap<_, _>, _>>()?;
 
-    let instructions = instructions_raw.trim().chars().collect();
+    let instructions = instructions_raw.text.trim().chars().collect();
 
     Ok((instructions, nodes))
 }
 
-fn parse_node(line: &str) -> GenResult<(String, Node)> {
-    let (current_raw, node_str) = line
-        .split_once('=')
-        .ok_or(format!("Expected '=': {}", line))?;
+fn parse_node(line: &Line) -> GenResult<(String, Node)> {
+    let (current_raw, node_str) = line.split_pair(line.text, '=')?;
 
-    let (left_raw, right_raw) = node_str
-        .trim()
-        .trim_start_matches('(')
-        .trim_end_matches(')')
-        .split_once(',')
-        .ok_or(format!("Invalid node string: {}", node_str))?;
+    let (left_raw, right_raw) = line.split_pair(
+        node_str
+            .trim()
+            .trim_start_matches('(')
+            .trim_end_matches(')'),
+        ',',
+    )?;
 
     Ok((
         current_raw.trim().to_string(),
@@ -82,19 +65,19 @@ fn parse_node(line: &str) -> GenResult<(String, Node)> {
     ))
 }
 
-fn traverse_all_paths(instructions: &Vec<char>, network: &HashMap<String, Node>) -> GenResult<i64> {
+fn traverse_all_paths(instructions: &[char], network: &HashMap<String, Node>) -> GenResult<i64> {
     let starting_nodes: Vec<&String> = network.keys().filter(|s| s.ends_with('A')).collect();
 
     let path_lengths = starting_nodes
         .iter()
-        .map(|s| traverse_network(instructions, &network, s, |s| s.ends_with('Z')))
+        .map(|s| traverse_network(instructions, network, s, |s| s.ends_with('Z')))
         .collect::<Result<Vec<_>, _>>()?;
 
     Ok(path_lengths.iter().fold(1, |acc, n| lcm(acc, *n)))
 }
 
 fn traverse_network(
-    instructions: &Vec<char>,
+    instructions: &[char],
     network: &HashMap<String, Node>,
     start: &str,
     end_cond: impl Fn(&str) -> bool,
@@ -1,46 +1,26 @@
-use std::error::Error;
-use std::fs::read_to_string;
+use aoc_core::{lines, GenResult, Line, Solution};
 
-type GenResult<T> = Result<T, Box<dyn Error>>;
+pub struct Day9;
 
-fn read_lines() -> GenResult<Vec<String>> {
-    Ok(read_to_string("input.txt")?
-        .lines()
-        .map(String::from)
-        .collect())
-}
-
-fn main() -> GenResult<()> {
-    let input = read_lines()?;
-
-    part1(&input)?;
-    part2(&input)?;
-
-    Ok(())
-}
-
-fn part1(input: &Vec<String>) -> GenResult<()> {
-    let result = compute_result(input, false)?;
-
-    println!("part 1: {}", result);
-
-    Ok(())
-}
+impl Solution for Day9 {
+    type Parsed = Vec<Vec<i32>>;
+    type Answer1 = i32;
+    type Answer2 = i32;
 
-fn part2(input: &Vec<String>) -> GenResult<()> {
-    let result = compute_result(input, true)?;
+    fn parse(input: &str) -> GenResult<Vec<Vec<i32>>> {
+        lines(input).map(|line| parse_line(&line)).collect()
+    }
 
-    println!("part 2: {}", result);
+    fn part1(all_nums: &Vec<Vec<i32>>) -> GenResult<i32> {
+        compute_result(all_nums, false)
+    }
 
-    Ok(())
+    fn part2(all_nums: &Vec<Vec<i32>>) -> GenResult<i32> {
+        compute_result(all_nums, true)
+    }
 }
 
-fn compute_result(input: &Vec<String>, is_prev: bool) -> GenResult<i32> {
-    let all_nums = input
-        .iter()
-        .map(|s| parse_line(s))
-        .collect::<Result<Vec<_>, _>>()?;
-
+fn compute_result(all_nums: &[Vec<i32>], is_prev: bool) -> GenResult<i32> {
     let values = all_nums
         .iter()
         .map(|nums| extrapolate(nums, is_prev))
@@ -49,14 +29,11 @@ fn compute_result(input: &Vec<String>, is_prev: bool) -> GenResult<i32> {
     Ok(values.iter().sum())
 }
 
-fn parse_line(line: &str) -> GenResult<Vec<i32>> {
-    Ok(line
-        .split_whitespace()
-        .map(|num| num.parse::<i32>())
-        .collect::<Result<Vec<_>, _>>()?)
+fn parse_line(line: &Line) -> GenResult<Vec<i32>> {
+    line.parse_nums(line.text)
 }
 
-fn extrapolate(nums: &Vec<i32>, is_prev: bool) -> GenResult<i32> {
+fn extrapolate(nums: &[i32], is_prev: bool) -> GenResult<i32> {
     if nums.iter().all(|&n| n == 0) {
         return Ok(0);
     }
@@ -73,7 +50,7 @@ fn extrapolate(nums: &Vec<i32>, is_prev: bool) -> GenResult<i32> {
     Ok(extrapolated)
 }
 
-fn compute_diffs(nums: &Vec<i32>) -> GenResult<Vec<i32>> {
+fn compute_diffs(nums: &[i32]) -> GenResult<Vec<i32>> {
     Ok(nums
         .windows(2)
         .map(|window| match window {